@@ -0,0 +1,124 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+#[cfg(feature = "compute-cache")]
+use popoki::ComputeCache;
+use popoki::{Correctness, Guess, Solver, Wordle, DICTIONARY};
+use std::{borrow::Cow, hint::black_box};
+
+/// Pairs of dictionary indices spread over the dictionary, the same on every run.
+fn pairs(n: usize) -> Vec<(usize, usize)> {
+    // Stepping by two large primes visits the dictionary in two unrelated orders.
+    (0..n)
+        .map(|i| (i * 7_919 % DICTIONARY.len(), i * 104_729 % DICTIONARY.len()))
+        .collect()
+}
+
+fn compute(c: &mut Criterion) {
+    let pairs: Vec<_> = pairs(1024)
+        .into_iter()
+        .map(|(answer, guess)| (DICTIONARY[answer].0, DICTIONARY[guess].0))
+        .collect();
+    c.bench_function("compute 1024 random pairs", |b| {
+        b.iter(|| {
+            for &(answer, guess) in &pairs {
+                black_box(Correctness::compute(black_box(answer), black_box(guess)));
+            }
+        })
+    });
+}
+
+/// Looking masks up in a `ComputeCache` against computing them, as `Solver` does with and
+/// without `Options::with_compute_cache`. Random pairs mostly miss the CPU caches, which is
+/// the worst case for the table.
+#[cfg(feature = "compute-cache")]
+fn compute_cache(c: &mut Criterion) {
+    let words: Vec<&str> = DICTIONARY.iter().map(|&(word, _)| word).collect();
+    let cache = ComputeCache::build(&words);
+    let pairs = pairs(1024);
+    let mut group = c.benchmark_group("1024 random pairs");
+    group.bench_function("compute_packed", |b| {
+        b.iter(|| {
+            for &(answer, guess) in &pairs {
+                black_box(Correctness::compute_packed(
+                    black_box(words[answer]),
+                    black_box(words[guess]),
+                ));
+            }
+        })
+    });
+    group.bench_function("ComputeCache::get", |b| {
+        b.iter(|| {
+            for &(answer, guess) in &pairs {
+                black_box(cache.get(black_box(guess), black_box(answer)));
+            }
+        })
+    });
+    group.finish();
+}
+
+/// Guesses after which many, some and few words are left.
+fn filters() -> Vec<Guess<'static>> {
+    [("trace", "humph"), ("crane", "shire"), ("sissy", "missy")]
+        .into_iter()
+        .map(|(guess, answer)| Guess {
+            word: Cow::Borrowed(guess),
+            mask: Correctness::compute(answer, guess),
+        })
+        .collect()
+}
+
+fn matches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("filter the dictionary");
+    for guess in filters() {
+        group.bench_function(format!("Guess::matches {}", guess.word), |b| {
+            b.iter(|| {
+                DICTIONARY
+                    .iter()
+                    .filter(|&&(word, _)| black_box(&guess).matches(word))
+                    .count()
+            })
+        });
+        group.bench_function(format!("Guess::allows {}", guess.word), |b| {
+            b.iter(|| {
+                DICTIONARY
+                    .iter()
+                    .filter(|&&(word, _)| black_box(&guess).allows(word))
+                    .count()
+            })
+        });
+        // Preparing is done once per guess, so it is left out of the timing.
+        let prepared = guess.prepare();
+        group.bench_function(format!("PreparedGuess::allows {}", guess.word), |b| {
+            b.iter(|| {
+                DICTIONARY
+                    .iter()
+                    .filter(|&&(word, _)| black_box(&prepared).allows(word))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn game(c: &mut Criterion) {
+    let w = Wordle::new();
+    // The compute cache is allocated by the first solver built and filled in as games are
+    // played, so apart from the first few iterations this times games with a warm cache.
+    let mut group = c.benchmark_group("solver");
+    group.sample_size(10);
+    for answer in ["cigar", "humph", "watch"] {
+        group.bench_function(format!("game {}", answer), |b| {
+            b.iter_batched(
+                Solver::default,
+                |solver| w.play(black_box(answer), solver),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "compute-cache")]
+criterion_group!(benches, compute, compute_cache, matches, game);
+#[cfg(not(feature = "compute-cache"))]
+criterion_group!(benches, compute, matches, game);
+criterion_main!(benches);
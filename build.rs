@@ -11,11 +11,21 @@ fn main() {
         let (word, count) = line
             .split_once(' ')
             .expect("every line is word + space + frequency");
-        let count: usize = count.parse().expect("every count is a number");
+        let count: u64 = count.parse().expect("every count is a number");
         (word, count)
     }));
     words.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
 
+    // The most common words occur more often than a 32-bit `usize` (like on wasm32) can count,
+    // so scale every count down to fit. Only the ratios between counts matter to the solver.
+    let width: u32 = std::env::var("CARGO_CFG_TARGET_POINTER_WIDTH")
+        .expect("cargo sets the target pointer width")
+        .parse()
+        .expect("the pointer width is a number");
+    let max = 1u64.checked_shl(width).map_or(u64::MAX, |limit| limit - 1);
+    let scale = words[0].1 / max + 1;
+
+    writeln!(f, "#[allow(clippy::large_const_arrays)]").unwrap();
     writeln!(
         f,
         "pub const DICTIONARY: [(&str, usize); {}] = [",
@@ -23,7 +33,7 @@ fn main() {
     )
     .unwrap();
     for (word, count) in words {
-        writeln!(f, "(\"{}\", {}),", word, count).unwrap();
+        writeln!(f, "(\"{}\", {}),", word, (count / scale).max(1)).unwrap();
     }
     write!(f, "];").unwrap();
 }
@@ -0,0 +1,14 @@
+mod coverage;
+mod fallback;
+mod forced;
+mod memoized;
+mod minimax;
+mod random;
+mod tree;
+pub use coverage::Coverage;
+pub use fallback::Fallback;
+pub use forced::ForcedOpening;
+pub use memoized::{HistoryCache, Memoized};
+pub use minimax::Minimax;
+pub use random::Random;
+pub use tree::{TreeGuesser, TreeParseError};
@@ -0,0 +1,147 @@
+use crate::{Guess, Guesser, Options, Solver};
+
+/// How many opening turns are spent on new letters before handing over to the solver.
+const COVERAGE_TURNS: usize = 2;
+
+/// Spends the first two guesses on as many common letters as it can, like the human habit of
+/// always opening with the same pair of words, and then plays like [`Solver`].
+///
+/// Each opening guess is the word whose letters not yet played are most common in the
+/// dictionary, counting each letter where it sits in the word: an `s` is worth more at the
+/// start of a word than at the end. A letter repeated within the word only counts once. The
+/// feedback on these guesses is ignored until the solver takes over.
+#[derive(Clone)]
+pub struct Coverage {
+    /// How many dictionary words have each letter at each position.
+    positional: [[usize; 26]; 5],
+    solver: Solver,
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Coverage {
+    /// Cover letters from the bundled dictionary, then play like the default [`Solver`].
+    pub fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    /// Cover letters from the dictionary in `options`, then play like the solver they build.
+    pub fn with_options(options: Options) -> Self {
+        let mut positional = [[0; 26]; 5];
+        for (word, _) in options.words() {
+            for (counts, letter) in positional.iter_mut().zip(word.bytes()) {
+                counts[usize::from(letter - b'a')] += 1;
+            }
+        }
+        Self {
+            positional,
+            solver: options.build(),
+        }
+    }
+
+    /// The word that adds the most letter coverage on top of `history`. Ties go to the word
+    /// earlier in the dictionary.
+    fn widest(&self, history: &[Guess]) -> &str {
+        let mut played = 0u32;
+        for guess in history {
+            for letter in guess.word.bytes() {
+                played |= 1 << (letter - b'a');
+            }
+        }
+
+        let mut best: Option<(&str, usize)> = None;
+        for word in self.solver.words() {
+            let mut seen = played;
+            let mut score = 0;
+            for (counts, letter) in self.positional.iter().zip(word.bytes()) {
+                let bit = 1 << (letter - b'a');
+                if seen & bit == 0 {
+                    seen |= bit;
+                    score += counts[usize::from(letter - b'a')];
+                }
+            }
+            if best.is_none_or(|(_, s)| score > s) {
+                best = Some((word, score));
+            }
+        }
+        best.expect("dictionary is empty").0
+    }
+}
+
+impl Guesser for Coverage {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        if history.len() < COVERAGE_TURNS {
+            self.widest(history).to_string()
+        } else {
+            self.solver.guess(history)
+        }
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.solver.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.solver.reveal(position, letter);
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.solver.remaining()
+    }
+
+    fn reset(&mut self) {
+        self.solver.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Correctness, Wordle};
+    use std::{borrow::Cow, collections::HashSet};
+
+    #[test]
+    fn covers_nine_letters() {
+        let mut g = Coverage::new();
+        let first = g.guess(&[]);
+        let history = [Guess {
+            mask: Correctness::compute("hatch", &first),
+            word: Cow::Owned(first),
+        }];
+        let second = g.guess(&history);
+
+        let letters: HashSet<u8> = history[0].word.bytes().chain(second.bytes()).collect();
+        assert!(letters.len() >= 9, "{} {}", history[0].word, second);
+    }
+
+    #[test]
+    fn solves() {
+        let w = Wordle::new();
+        for answer in ["cigar", "humph", "awake"] {
+            let outcome = w.play_recording(answer, Coverage::new());
+            assert!(outcome.score().is_some(), "{}", answer);
+        }
+    }
+
+    #[test]
+    fn hints_reach_the_solver() {
+        let mut hinted = Coverage::new();
+        let mut plain = Coverage::new();
+        let mut history = Vec::new();
+        for _ in 0..COVERAGE_TURNS {
+            let word = plain.guess(&history);
+            history.push(Guess {
+                mask: Correctness::compute("cigar", &word),
+                word: Cow::Owned(word),
+            });
+        }
+        hinted.reveal(4, b'r');
+        hinted.guess(&history);
+        plain.guess(&history);
+        assert!(hinted.remaining() < plain.remaining());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Guess, Guesser, Wordle};
+
+/// Plays what one guesser suggests, except when it suggests a word that isn't in the
+/// dictionary, in which case another guesser is asked instead.
+///
+/// The check is made every turn, so the first guesser gets another chance on the next one. Both
+/// guessers see the whole history, so the fallback must cope with turns it didn't play;
+/// [`Solver`](crate::Solver) does.
+pub struct Fallback<'w, A, B> {
+    first: A,
+    fallback: B,
+    wordle: &'w Wordle,
+    /// Whether the latest guess came from `fallback`.
+    fell_back: bool,
+}
+
+impl<'w, A: Guesser, B: Guesser> Fallback<'w, A, B> {
+    /// Try `first`, falling back to `fallback` for guesses `wordle` wouldn't accept.
+    pub fn new(first: A, fallback: B, wordle: &'w Wordle) -> Self {
+        Self {
+            first,
+            fallback,
+            wordle,
+            fell_back: false,
+        }
+    }
+}
+
+impl<A: Guesser, B: Guesser> Guesser for Fallback<'_, A, B> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        let guess = self.first.guess(history);
+        self.fell_back = !self.wordle.contains(&guess);
+        if self.fell_back {
+            self.fallback.guess(history)
+        } else {
+            guess
+        }
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.first.finish(guesses);
+        self.fallback.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.first.reveal(position, letter);
+        self.fallback.reveal(position, letter);
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.fallback.reset();
+        self.fell_back = false;
+    }
+
+    /// What the guesser that made the latest guess says.
+    fn remaining(&self) -> Option<usize> {
+        if self.fell_back {
+            self.fallback.remaining()
+        } else {
+            self.first.remaining()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solver;
+
+    #[test]
+    fn falls_back_on_unknown_words() {
+        let w = Wordle::new();
+        let broken = guesser!(|_history| { "qxzzy".to_string() });
+        let history = w
+            .play_recording("humph", Fallback::new(broken, Solver::default(), &w))
+            .into_history();
+        let plain = w.play_recording("humph", Solver::default()).into_history();
+        assert_eq!(history, plain);
+    }
+
+    #[test]
+    fn first_gets_another_chance() {
+        let w = Wordle::new();
+        // Only every other guess is a real word.
+        let flaky = guesser!(|history| { ["qxzzy", "crane"][history.len() % 2].to_string() });
+        let history = w
+            .play_recording("cigar", Fallback::new(flaky, Solver::default(), &w))
+            .into_history();
+        assert_eq!(history.last().unwrap().word, "cigar");
+        assert_ne!(history[0].word, "qxzzy");
+        if history.len() > 1 {
+            assert_eq!(history[1].word, "crane");
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Guess, Guesser, UnknownOpener, DICTIONARY};
+
+/// Opens with a fixed list of words, whatever the feedback, and then hands over to another
+/// guesser for the rest of the game.
+///
+/// The inner guesser is first asked for a guess once the script has run out, so it has to cope
+/// with turns it didn't play; [`Solver`](crate::Solver) does.
+pub struct ForcedOpening<G> {
+    script: Vec<&'static str>,
+    inner: G,
+}
+
+impl<G: Guesser> ForcedOpening<G> {
+    /// Play `script` first, then `inner`. Every scripted word has to be in the bundled
+    /// dictionary.
+    pub fn new(script: &[&'static str], inner: G) -> Result<Self, UnknownOpener> {
+        Self::with_dictionary(script, inner, &DICTIONARY)
+    }
+
+    /// Like [`ForcedOpening::new`], but checks the script against `dictionary` instead.
+    pub fn with_dictionary(
+        script: &[&'static str],
+        inner: G,
+        dictionary: &[(&'static str, usize)],
+    ) -> Result<Self, UnknownOpener> {
+        if let Some(&opener) = script
+            .iter()
+            .find(|&&word| !dictionary.iter().any(|&(w, _)| w == word))
+        {
+            return Err(UnknownOpener { opener });
+        }
+        Ok(Self {
+            script: script.to_vec(),
+            inner,
+        })
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+}
+
+impl<G: Guesser> Guesser for ForcedOpening<G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        match self.script.get(history.len()) {
+            Some(word) => word.to_string(),
+            None => self.inner.guess(history),
+        }
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.inner.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.inner.reveal(position, letter);
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.inner.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Solver, Wordle};
+
+    #[test]
+    fn script_then_inner() {
+        let w = Wordle::new();
+        let g = ForcedOpening::new(&["salet", "court"], Solver::default()).unwrap();
+        let history = w.play_recording("nymph", g).into_history();
+        let words: Vec<_> = history.iter().map(|g| &*g.word).collect();
+        assert_eq!(&words[..2], ["salet", "court"]);
+        assert_eq!(words[2], Solver::default().guess(&history[..2]));
+        assert_eq!(words.last(), Some(&"nymph"));
+    }
+
+    #[test]
+    fn unknown_word() {
+        assert_eq!(
+            ForcedOpening::new(&["salet", "qxzzy"], Solver::default()).err(),
+            Some(UnknownOpener { opener: "qxzzy" })
+        );
+    }
+}
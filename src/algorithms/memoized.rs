@@ -0,0 +1,395 @@
+use crate::{Correctness, Guess, Guesser};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// A bounded, least-recently-used map from a game history to the guess made for it, along with
+/// how many candidates the guesser had left (see [`Guesser::remaining`]).
+///
+/// Clones share the same underlying cache, so one `HistoryCache` can be handed to the guessers
+/// of many games (even across threads). Only share a cache between guessers that would make
+/// the same choices, since it can't tell them apart.
+#[derive(Debug, Clone)]
+pub struct HistoryCache {
+    inner: Arc<Mutex<Lru>>,
+}
+
+/// Every guess and mask in order, so the same guesses in a different order are a different
+/// history.
+type Key = Vec<(String, [Correctness; 5])>;
+
+/// A cached guess and the remaining candidate count the guesser reported with it.
+type Entry = (String, Option<usize>);
+
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    slots: HashMap<Key, usize>,
+    /// The entries, linked from least (`head`) to most (`tail`) recently used by index.
+    nodes: Vec<Node>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+#[derive(Debug)]
+struct Node {
+    key: Key,
+    guess: Entry,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+impl HistoryCache {
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert_ne!(
+            capacity, 0,
+            "a history cache must be able to hold something"
+        );
+        Self {
+            inner: Arc::new(Mutex::new(Lru {
+                capacity,
+                slots: HashMap::with_capacity(capacity),
+                nodes: Vec::with_capacity(capacity),
+                head: None,
+                tail: None,
+            })),
+        }
+    }
+
+    /// The number of histories currently cached.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &Key) -> Option<Entry> {
+        let mut lru = self.inner.lock().unwrap();
+        let idx = *lru.slots.get(key)?;
+        lru.touch(idx);
+        Some(lru.nodes[idx].guess.clone())
+    }
+
+    fn insert(&self, key: Key, guess: Entry) {
+        let mut lru = self.inner.lock().unwrap();
+        if let Some(&idx) = lru.slots.get(&key) {
+            lru.nodes[idx].guess = guess;
+            lru.touch(idx);
+            return;
+        }
+        let idx = if lru.nodes.len() < lru.capacity {
+            lru.nodes.push(Node {
+                key: key.clone(),
+                guess,
+                prev: None,
+                next: None,
+            });
+            lru.nodes.len() - 1
+        } else {
+            // Reuse the least recently used entry's node.
+            let idx = lru.head.expect("a full cache has entries");
+            lru.unlink(idx);
+            let node = &mut lru.nodes[idx];
+            let evicted = std::mem::replace(&mut node.key, key.clone());
+            node.guess = guess;
+            lru.slots.remove(&evicted);
+            idx
+        };
+        lru.slots.insert(key, idx);
+        lru.push_back(idx);
+    }
+}
+
+impl Lru {
+    /// Mark the entry at `idx` as the most recently used.
+    fn touch(&mut self, idx: usize) {
+        self.unlink(idx);
+        self.push_back(idx);
+    }
+
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next) = (self.nodes[idx].prev, self.nodes[idx].next);
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_back(&mut self, idx: usize) {
+        self.nodes[idx].prev = self.tail;
+        self.nodes[idx].next = None;
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(idx),
+            None => self.head = Some(idx),
+        }
+        self.tail = Some(idx);
+    }
+}
+
+fn history_key(history: &[Guess]) -> Key {
+    history
+        .iter()
+        .map(|guess| (guess.word.to_string(), guess.mask))
+        .collect()
+}
+
+/// Wraps a guesser so that a history seen before is answered from a [`HistoryCache`] instead.
+///
+/// The inner guesser is not asked at all on a hit, so it must cope with skipped turns;
+/// [`Solver`](crate::Solver) does. Once a letter has been revealed (see [`Guesser::reveal`]) the
+/// history no longer says everything the inner guesser knows, so the rest of that game bypasses
+/// the cache.
+pub struct Memoized<G> {
+    inner: G,
+    cache: HistoryCache,
+    remaining: Option<usize>,
+    revealed: bool,
+}
+
+impl<G: Guesser> Memoized<G> {
+    /// Memoize `inner` with a fresh cache holding up to `capacity` histories.
+    pub fn new(inner: G, capacity: usize) -> Self {
+        Self::with_cache(inner, HistoryCache::new(capacity))
+    }
+
+    /// Memoize `inner` with an existing, possibly shared, cache.
+    pub fn with_cache(inner: G, cache: HistoryCache) -> Self {
+        Self {
+            inner,
+            cache,
+            remaining: None,
+            revealed: false,
+        }
+    }
+
+    pub fn cache(&self) -> &HistoryCache {
+        &self.cache
+    }
+}
+
+impl<G: Guesser> Guesser for Memoized<G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        if self.revealed {
+            let guess = self.inner.guess(history);
+            self.remaining = self.inner.remaining();
+            return guess;
+        }
+        let key = history_key(history);
+        if let Some((guess, remaining)) = self.cache.get(&key) {
+            self.remaining = remaining;
+            return guess;
+        }
+        let guess = self.inner.guess(history);
+        self.remaining = self.inner.remaining();
+        self.cache.insert(key, (guess.clone(), self.remaining));
+        guess
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.inner.finish(guesses);
+    }
+
+    fn reset(&mut self) {
+        // The cache is keyed by the whole history, so it stays valid from one game to the next.
+        self.inner.reset();
+        self.remaining = None;
+        self.revealed = false;
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.remaining
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.revealed = true;
+        self.inner.reveal(position, letter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Correctness, Solver, Wordle};
+    use std::{borrow::Cow, cell::Cell, rc::Rc};
+
+    struct Counting {
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl Guesser for Counting {
+        fn guess(&mut self, history: &[Guess]) -> String {
+            self.calls.set(self.calls.get() + 1);
+            ["wrong", "right"][history.len() % 2].to_string()
+        }
+    }
+
+    fn guess(word: &'static str, answer: &str) -> Guess<'static> {
+        Guess {
+            word: Cow::Borrowed(word),
+            mask: Correctness::compute(answer, word),
+        }
+    }
+
+    #[test]
+    fn repeats_skip_the_inner_guesser() {
+        let calls = Rc::new(Cell::new(0));
+        let cache = HistoryCache::new(16);
+        let w = Wordle::new();
+        for _ in 0..3 {
+            let g = Memoized::with_cache(
+                Counting {
+                    calls: Rc::clone(&calls),
+                },
+                cache.clone(),
+            );
+            assert_eq!(w.play("right", g), Some(2));
+        }
+        assert_eq!(calls.get(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn order_matters() {
+        let mut g = Memoized::new(
+            Counting {
+                calls: Rc::new(Cell::new(0)),
+            },
+            16,
+        );
+        g.guess(&[guess("trace", "right"), guess("sight", "right")]);
+        g.guess(&[guess("sight", "right"), guess("trace", "right")]);
+        assert_eq!(g.cache().len(), 2);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let calls = Rc::new(Cell::new(0));
+        let mut g = Memoized::new(
+            Counting {
+                calls: Rc::clone(&calls),
+            },
+            2,
+        );
+        let a = [guess("trace", "right")];
+        let b = [guess("sight", "right")];
+        let c = [guess("night", "right")];
+        g.guess(&a);
+        g.guess(&b);
+        g.guess(&a);
+        g.guess(&c); // evicts b
+        assert_eq!(calls.get(), 3);
+        g.guess(&a);
+        assert_eq!(calls.get(), 3);
+        g.guess(&b);
+        assert_eq!(calls.get(), 4);
+    }
+
+    #[test]
+    fn same_as_a_list_in_use_order() {
+        let words = ["trace", "sight", "night", "wrong", "right"];
+        let cache = HistoryCache::new(3);
+        // The cached words, least recently used first.
+        let mut expected: Vec<&str> = Vec::new();
+        for i in 0..40 {
+            let word = words[(i * 7 + i / 3) % words.len()];
+            let key = history_key(&[guess(word, "right")]);
+            let hit = cache.get(&key);
+            assert_eq!(
+                hit.as_ref().map(|(guess, _)| guess.as_str()),
+                expected.contains(&word).then_some(word)
+            );
+            expected.retain(|&w| w != word);
+            if hit.is_none() {
+                cache.insert(key, (word.to_string(), None));
+                if expected.len() == 3 {
+                    expected.remove(0);
+                }
+            }
+            expected.push(word);
+            assert_eq!(cache.len(), expected.len());
+        }
+    }
+
+    #[test]
+    fn memoized_solver_plays_the_same() {
+        let w = Wordle::new();
+        let cache = HistoryCache::new(1024);
+        for answer in ["cigar", "rebut", "sissy", "humph", "awake"] {
+            let plain = w.play(answer, Solver::default());
+            let memoized = w.play(
+                answer,
+                Memoized::with_cache(Solver::default(), cache.clone()),
+            );
+            assert_eq!(plain, memoized, "{}", answer);
+        }
+        // Every game shares at least the opener.
+        assert!(cache.len() < 5 * 3);
+    }
+
+    #[test]
+    fn remaining_survives_cache_hits() {
+        let w = Wordle::new();
+        let cache = HistoryCache::new(1024);
+        for answer in ["cigar", "rebut", "cigar"] {
+            let plain = w.play_verbose(answer, Solver::default());
+            let memoized = w.play_verbose(
+                answer,
+                Memoized::with_cache(Solver::default(), cache.clone()),
+            );
+            assert_eq!(plain, memoized, "{}", answer);
+            assert!(memoized.turns.iter().all(|t| t.remaining_after.is_some()));
+        }
+    }
+
+    #[test]
+    fn reveals_reach_the_inner_guesser() {
+        let w = Wordle::new();
+        let cache = HistoryCache::new(1024);
+        for answer in ["cigar", "rebut", "sissy", "humph", "awake"] {
+            // Warm the cache with unhinted games first, so a hinted game would go wrong if it
+            // were answered from it.
+            w.play(
+                answer,
+                Memoized::with_cache(Solver::default(), cache.clone()),
+            );
+            let plain = w.play_with_hints(answer, Solver::default(), 1);
+            let memoized = w.play_with_hints(
+                answer,
+                Memoized::with_cache(Solver::default(), cache.clone()),
+                1,
+            );
+            assert_eq!(plain, memoized, "{}", answer);
+        }
+    }
+
+    #[test]
+    fn reveals_bypass_the_cache() {
+        let calls = Rc::new(Cell::new(0));
+        let cache = HistoryCache::new(16);
+        let history = [guess("trace", "right")];
+        let mut g = Memoized::with_cache(
+            Counting {
+                calls: Rc::clone(&calls),
+            },
+            cache.clone(),
+        );
+        g.guess(&history);
+        g.reveal(0, b'r');
+        g.guess(&history);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(cache.len(), 1);
+        g.reset();
+        g.guess(&history);
+        assert_eq!(calls.get(), 2);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{partition, Filter, Guess, Guesser, DICTIONARY};
+
+/// Guesses the word whose worst-case feedback leaves the fewest candidates (Knuth-style
+/// minimax), rather than the one with the most expected information like [`Solver`].
+///
+/// Every word in the dictionary is considered, not just the remaining candidates, since a word
+/// that can't be the answer sometimes splits them better. Among equally good words, one that
+/// could still be the answer is preferred, and then the one earlier in the dictionary. Word
+/// frequencies are ignored: each candidate counts once.
+///
+/// Each turn tries every word against every candidate, so the first guess on the bundled
+/// dictionary takes a while.
+///
+/// This plays by the same rule as [`WorstCase`](crate::WorstCase), which only scores a split.
+///
+/// [`Solver`]: crate::Solver
+#[derive(Debug, Clone)]
+pub struct Minimax {
+    dictionary: &'static [(&'static str, usize)],
+    candidates: Filter,
+    /// How many history entries `candidates` has seen.
+    seen: usize,
+}
+
+impl Default for Minimax {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Minimax {
+    /// Play with the bundled dictionary.
+    pub fn new() -> Self {
+        Self::with_dictionary(&DICTIONARY)
+    }
+
+    /// Play with `dictionary` instead, as both the words to guess and the possible answers.
+    pub fn with_dictionary(dictionary: &'static [(&'static str, usize)]) -> Self {
+        Self {
+            dictionary,
+            candidates: Filter::new(dictionary.iter().map(|&(word, _)| word)),
+            seen: 0,
+        }
+    }
+
+    /// The size of the largest set of candidates `guess` could leave.
+    fn worst_case(&self, guess: &str, candidates: &[(&str, usize)]) -> usize {
+        partition(guess, candidates).into_iter().max().unwrap_or(0)
+    }
+}
+
+impl Guesser for Minimax {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        if history.len() < self.seen {
+            // A different game, or an earlier point in this one, so start over.
+            self.candidates = Filter::new(self.dictionary.iter().map(|&(word, _)| word));
+            self.seen = 0;
+        }
+        for guess in &history[self.seen..] {
+            self.candidates.observe(guess);
+        }
+        self.seen = history.len();
+        if self.candidates.len() == 1 {
+            return self.candidates.iter().next().unwrap().to_string();
+        }
+
+        let candidates: Vec<(&str, usize)> = self.candidates.iter().map(|w| (w, 1)).collect();
+        let possible = |word| candidates.iter().any(|&(w, _)| w == word);
+        let mut best: Option<(&str, usize, bool)> = None;
+        for &(word, _) in self.dictionary {
+            let worst = self.worst_case(word, &candidates);
+            let better = match best {
+                None => true,
+                Some((_, w, _)) if worst < w => true,
+                Some((_, w, p)) => worst == w && !p && possible(word),
+            };
+            if better {
+                best = Some((word, worst, possible(word)));
+            }
+        }
+        best.expect("dictionary is empty").0.to_string()
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.candidates.len())
+    }
+
+    fn reset(&mut self) {
+        *self = Self::with_dictionary(self.dictionary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{best_opener_by, partition, Correctness, Entropy, Wordle};
+
+    const DICT: [(&str, usize); 10] = [
+        ("loads", 1),
+        ("twigs", 1),
+        ("fermi", 1),
+        ("tight", 1),
+        ("crook", 1),
+        ("bulls", 1),
+        ("along", 1),
+        ("edged", 1),
+        ("refer", 1),
+        ("abuse", 1),
+    ];
+
+    fn buckets(guess: &str) -> Vec<usize> {
+        let mut buckets: Vec<usize> = partition(guess, &DICT)
+            .into_iter()
+            .filter(|&b| b != 0)
+            .collect();
+        buckets.sort_unstable();
+        buckets
+    }
+
+    #[test]
+    fn differs_from_entropy() {
+        // `loads` tells seven of the words apart outright, but leaves the other three together:
+        // about 2.85 bits on average, and the most of any word.
+        let (entropy, bits) = best_opener_by(&DICT, &Entropy);
+        assert_eq!(entropy, "loads");
+        assert!((bits - 2.846).abs() < 1e-3);
+        assert_eq!(buckets("loads"), [1, 1, 1, 1, 1, 1, 1, 3]);
+
+        // `twigs` only manages about 2.72 bits, but never leaves more than two.
+        let minimax = Minimax::with_dictionary(&DICT).guess(&[]);
+        assert_eq!(minimax, "twigs");
+        assert_eq!(buckets("twigs"), [1, 1, 1, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    fn solves_within_the_worst_case() {
+        let w = Wordle::from_dictionary(
+            &DICT
+                .iter()
+                .map(|(word, count)| format!("{} {}\n", word, count))
+                .collect::<String>(),
+        )
+        .unwrap();
+        for (answer, _) in DICT {
+            let score = w.play(answer, Minimax::with_dictionary(&DICT));
+            assert!(matches!(score, Some(1..=3)), "{}: {:?}", answer, score);
+        }
+    }
+
+    #[test]
+    fn prefers_a_possible_answer() {
+        let mut g = Minimax::with_dictionary(&DICT);
+        let history = [Guess {
+            word: "twigs".into(),
+            mask: Correctness::compute("loads", "twigs"),
+        }];
+        let guess = g.guess(&history);
+        assert_eq!(g.remaining(), Some(2));
+        assert!(history[0].matches(&guess), "{}", guess);
+    }
+
+    #[test]
+    fn shorter_history_starts_over() {
+        let mut g = Minimax::with_dictionary(&DICT);
+        let history = [
+            Guess {
+                word: "twigs".into(),
+                mask: Correctness::compute("loads", "twigs"),
+            },
+            Guess {
+                word: "along".into(),
+                mask: Correctness::compute("loads", "along"),
+            },
+        ];
+        g.guess(&history);
+        assert_eq!(g.remaining(), Some(1));
+        g.guess(&history[..1]);
+        assert_eq!(g.remaining(), Some(2));
+        g.guess(&[]);
+        assert_eq!(g.remaining(), Some(DICT.len()));
+    }
+}
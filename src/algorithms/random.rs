@@ -0,0 +1,117 @@
+use crate::{splitmix64, Filter, Guess, Guesser, DICTIONARY};
+
+/// Guesses a random word that could still be the answer, as a baseline for other guessers to
+/// beat.
+///
+/// The words are drawn from a small PRNG seeded with the value passed in, so the same seed
+/// always plays the same game.
+#[derive(Debug, Clone)]
+pub struct Random {
+    dictionary: &'static [(&'static str, usize)],
+    seed: u64,
+    state: u64,
+    candidates: Filter,
+    /// How many history entries `candidates` has seen.
+    seen: usize,
+}
+
+impl Random {
+    /// Guess from the bundled dictionary.
+    pub fn new(seed: u64) -> Self {
+        Self::with_dictionary(&DICTIONARY, seed)
+    }
+
+    /// Guess from `dictionary` instead.
+    pub fn with_dictionary(dictionary: &'static [(&'static str, usize)], seed: u64) -> Self {
+        Self {
+            dictionary,
+            seed,
+            state: seed,
+            candidates: Filter::new(dictionary.iter().map(|&(word, _)| word)),
+            seen: 0,
+        }
+    }
+}
+
+impl Guesser for Random {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        if history.len() < self.seen {
+            // A different game, or an earlier point in this one, so start over.
+            self.candidates = Filter::new(self.dictionary.iter().map(|&(word, _)| word));
+            self.seen = 0;
+        }
+        for guess in &history[self.seen..] {
+            self.candidates.observe(guess);
+        }
+        self.seen = history.len();
+
+        let len = self.candidates.len() as u64;
+        assert_ne!(len, 0, "no word fits the history");
+        // The modulo bias is far too small to matter for a baseline.
+        let pick = (splitmix64(&mut self.state) % len) as usize;
+        self.candidates.iter().nth(pick).unwrap().to_string()
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.candidates.len())
+    }
+
+    fn reset(&mut self) {
+        *self = Self::with_dictionary(self.dictionary, self.seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Correctness, Wordle};
+    use std::borrow::Cow;
+
+    #[test]
+    fn same_seed_same_game() {
+        let w = Wordle::new();
+        for answer in ["cigar", "humph"] {
+            let a = w.play_recording(answer, Random::new(42));
+            let b = w.play_recording(answer, Random::new(42));
+            assert_eq!(a, b);
+            assert!(a.score().is_some());
+        }
+        assert_ne!(
+            w.play_recording("cigar", Random::new(42)),
+            w.play_recording("cigar", Random::new(43))
+        );
+    }
+
+    #[test]
+    fn reset_replays() {
+        let w = Wordle::new();
+        let mut g = Random::new(7);
+        let answers = ["cigar", "cigar"];
+        let stats = w.evaluate_reusing(&mut g, &answers);
+        assert_eq!(stats.histogram.iter().filter(|&&n| n != 0).count(), 1);
+        let fresh = w.evaluate(|| Random::new(7), &answers);
+        assert_eq!(
+            (stats.histogram, stats.failures),
+            (fresh.histogram, fresh.failures)
+        );
+    }
+
+    #[test]
+    fn shorter_history_starts_over() {
+        let mut g = Random::new(7);
+        let history: Vec<_> = ["trace", "sight"]
+            .into_iter()
+            .map(|word| Guess {
+                word: Cow::Borrowed(word),
+                mask: Correctness::compute("right", word),
+            })
+            .collect();
+        g.guess(&history);
+        let after_two = g.remaining();
+        g.guess(&history[..1]);
+        let after_one = g.remaining();
+        assert!(after_two < after_one);
+        g.guess(&[]);
+        assert_eq!(g.remaining(), Some(DICTIONARY.len()));
+    }
+}
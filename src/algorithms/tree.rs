@@ -0,0 +1,258 @@
+use crate::{check_word, Correctness, Guess, Guesser, ParseMaskError, WordError};
+use std::{collections::HashMap, fmt, fs, io, path::Path};
+
+/// Why a decision tree could not be parsed, see [`TreeGuesser::parse`]. Lines are numbered
+/// from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeParseError {
+    /// The line has no `->` before the guess.
+    MissingArrow { line: usize },
+    /// A step of the path isn't written as `word:mask`.
+    MissingColon { line: usize },
+    /// A word on the line, in the path or the guess, isn't a five-letter lowercase word.
+    Word { line: usize, error: WordError },
+    /// A mask in the path isn't five colors, see [`Correctness::from_chars`].
+    Mask { line: usize, error: ParseMaskError },
+}
+
+impl TreeParseError {
+    /// The line the problem was found on.
+    pub fn line(&self) -> usize {
+        match *self {
+            Self::MissingArrow { line }
+            | Self::MissingColon { line }
+            | Self::Word { line, .. }
+            | Self::Mask { line, .. } => line,
+        }
+    }
+}
+
+impl fmt::Display for TreeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingArrow { line } => write!(f, "line {}: missing '->'", line),
+            Self::MissingColon { line } => {
+                write!(f, "line {}: path step is not 'word:mask'", line)
+            }
+            Self::Word { line, error } => write!(f, "line {}: {}", line, error),
+            Self::Mask { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for TreeParseError {}
+
+/// Plays from a precomputed decision tree: the guess to make after each sequence of guesses
+/// and masks. Histories the tree doesn't cover are handed to another guesser.
+///
+/// Trees are written one node per line, as the path to the node followed by `->` and the
+/// guess to make there. The path is every guess so far as `word:mask`, separated by spaces,
+/// with masks in the letters [`Correctness::from_chars`] reads. The opener has an empty path:
+///
+/// ```text
+/// -> trace
+/// trace:YWWWW -> right
+/// trace:YWWWW right:WGGGG -> fight
+/// ```
+///
+/// Blank lines are skipped, and a later line for the same path replaces an earlier one.
+pub struct TreeGuesser<G> {
+    /// The guess for each path, keyed by the path as it is written out.
+    moves: HashMap<String, String>,
+    inner: G,
+}
+
+impl<G: Guesser> TreeGuesser<G> {
+    /// An empty tree, which leaves every guess to `inner` until moves are added with
+    /// [`TreeGuesser::insert`].
+    pub fn new(inner: G) -> Self {
+        Self {
+            moves: HashMap::new(),
+            inner,
+        }
+    }
+
+    /// Read a tree written as described on [`TreeGuesser`], falling back to `inner` where it
+    /// ends.
+    pub fn parse(text: &str, inner: G) -> Result<Self, TreeParseError> {
+        let mut tree = Self::new(inner);
+        for (i, l) in text.lines().enumerate() {
+            let line = i + 1;
+            let l = l.trim();
+            if l.is_empty() {
+                continue;
+            }
+            let (path, guess) = l
+                .split_once("->")
+                .ok_or(TreeParseError::MissingArrow { line })?;
+            let word = |w| check_word(w, 5).map_err(|error| TreeParseError::Word { line, error });
+            let history = path
+                .split_whitespace()
+                .map(|step| {
+                    let (w, mask) = step
+                        .split_once(':')
+                        .ok_or(TreeParseError::MissingColon { line })?;
+                    Ok(Guess {
+                        word: word(w)?.into(),
+                        mask: Correctness::from_chars(mask)
+                            .map_err(|error| TreeParseError::Mask { line, error })?,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            tree.insert(&history, word(guess.trim())?);
+        }
+        Ok(tree)
+    }
+
+    /// Like [`TreeGuesser::parse`], reading the tree from the file at `path`. A malformed tree
+    /// is reported as an [`io::ErrorKind::InvalidData`] error.
+    pub fn from_path(path: &Path, inner: G) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text, inner).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Play `guess` whenever the game so far is `history`.
+    pub fn insert(&mut self, history: &[Guess], guess: &str) {
+        self.moves.insert(path_key(history), guess.to_string());
+    }
+
+    /// The number of histories the tree has a guess for.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Write the tree out in the format [`TreeGuesser::parse`] reads, shortest paths first.
+    pub fn to_text(&self) -> String {
+        let mut nodes: Vec<_> = self.moves.iter().collect();
+        nodes.sort_by(|(a, _), (b, _)| (a.len(), a).cmp(&(b.len(), b)));
+        nodes
+            .into_iter()
+            .map(|(path, guess)| {
+                if path.is_empty() {
+                    format!("-> {}\n", guess)
+                } else {
+                    format!("{} -> {}\n", path, guess)
+                }
+            })
+            .collect()
+    }
+}
+
+fn path_key(history: &[Guess]) -> String {
+    history
+        .iter()
+        .map(|guess| format!("{}:{}", guess.word, Correctness::to_chars(&guess.mask)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl<G: Guesser> Guesser for TreeGuesser<G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        match self.moves.get(&path_key(history)) {
+            Some(guess) => guess.clone(),
+            None => self.inner.guess(history),
+        }
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.inner.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.inner.reveal(position, letter);
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.inner.remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Solver, Wordle};
+    use std::borrow::Cow;
+
+    fn guess(word: &'static str, answer: &str) -> Guess<'static> {
+        Guess {
+            word: Cow::Borrowed(word),
+            mask: Correctness::compute(answer, word),
+        }
+    }
+
+    #[test]
+    fn follows_the_tree() {
+        let mut tree =
+            TreeGuesser::new(guesser!(|_history| { panic!("the tree covers this game") }));
+        tree.insert(&[], "trace");
+        tree.insert(&[guess("trace", "fight")], "right");
+        tree.insert(&[guess("trace", "fight"), guess("right", "fight")], "fight");
+        // A branch the game never takes.
+        tree.insert(&[guess("trace", "crane")], "crane");
+        assert_eq!(tree.len(), 4);
+
+        let history = Wordle::new().play_recording("fight", tree).into_history();
+        let words: Vec<_> = history.iter().map(|g| &*g.word).collect();
+        assert_eq!(words, ["trace", "right", "fight"]);
+    }
+
+    #[test]
+    fn parses_what_it_writes() {
+        let text = "-> trace\ntrace:YWWWW -> right\n\ntrace:ywwww right:wgggg->fight\n";
+        let tree = TreeGuesser::parse(text, Solver::default()).unwrap();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(
+            tree.to_text(),
+            "-> trace\ntrace:YWWWW -> right\ntrace:YWWWW right:WGGGG -> fight\n"
+        );
+        let again = TreeGuesser::parse(&tree.to_text(), Solver::default()).unwrap();
+        assert_eq!(again.moves, tree.moves);
+    }
+
+    #[test]
+    fn falls_back_off_the_tree() {
+        let tree = TreeGuesser::parse("-> crane\n", Solver::default()).unwrap();
+        let history = Wordle::new().play_recording("humph", tree).into_history();
+        assert_eq!(history[0].word, "crane");
+        assert_eq!(history[1].word, Solver::default().guess(&history[..1]));
+        assert_eq!(history.last().unwrap().word, "humph");
+    }
+
+    #[test]
+    fn malformed() {
+        let parse = |text| TreeGuesser::parse(text, Solver::default()).err();
+        assert_eq!(
+            parse("-> trace\ntrace"),
+            Some(TreeParseError::MissingArrow { line: 2 })
+        );
+        assert_eq!(
+            parse("trace WYWWY -> right"),
+            Some(TreeParseError::MissingColon { line: 1 })
+        );
+        assert_eq!(
+            parse("trace:WYWWY -> Right"),
+            Some(TreeParseError::Word {
+                line: 1,
+                error: WordError::NotLowercase {
+                    index: 0,
+                    found: 'R'
+                }
+            })
+        );
+        assert_eq!(
+            parse("trace:WYWW -> right"),
+            Some(TreeParseError::Mask {
+                line: 1,
+                error: ParseMaskError::WrongLength { got: 4 }
+            })
+        );
+    }
+}
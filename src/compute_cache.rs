@@ -0,0 +1,91 @@
+use crate::{Correctness, PackedCorrectness};
+use std::fmt;
+
+/// The correctness of every guess against every answer over a fixed word list, computed up
+/// front.
+///
+/// This takes `words.len()²` bytes, which is around 170MB for the bundled dictionary, so it is
+/// only worth it when the same pairs are compared many times, like over a benchmark run.
+pub struct ComputeCache {
+    len: usize,
+    table: Vec<PackedCorrectness>,
+}
+
+impl ComputeCache {
+    /// Compute the table for `words`, which are then referred to by their index in it.
+    pub fn build(words: &[&str]) -> Self {
+        let table = words
+            .iter()
+            .flat_map(|guess| {
+                words
+                    .iter()
+                    .map(move |answer| Correctness::compute_packed(answer, guess))
+            })
+            .collect();
+        Self {
+            len: words.len(),
+            table,
+        }
+    }
+
+    /// The correctness of `words[guess_idx]` as a guess for `words[answer_idx]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn get(&self, guess_idx: usize, answer_idx: usize) -> PackedCorrectness {
+        assert!(
+            answer_idx < self.len,
+            "answer index {} out of bounds",
+            answer_idx
+        );
+        self.table[guess_idx * self.len + answer_idx]
+    }
+
+    /// How many words the table covers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl fmt::Debug for ComputeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The table itself is far too long to print.
+        f.debug_struct("ComputeCache")
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_compute() {
+        let words = ["right", "wrong", "night", "abbey", "kebab", "babes"];
+        let cache = ComputeCache::build(&words);
+        assert_eq!(cache.len(), words.len());
+        for (g, guess) in words.iter().enumerate() {
+            for (a, answer) in words.iter().enumerate() {
+                assert_eq!(
+                    cache.get(g, a),
+                    Correctness::compute_packed(answer, guess),
+                    "{} guessed for {}",
+                    guess,
+                    answer
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        ComputeCache::build(&["right", "wrong"]).get(0, 2);
+    }
+}
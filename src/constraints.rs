@@ -0,0 +1,236 @@
+use crate::{Correctness, Guess};
+
+const LETTERS: usize = (b'z' - b'a' + 1) as usize;
+
+/// Everything a history of guesses says about the answer, folded into fixed-size tables.
+///
+/// Checking a word against `Constraints` needs neither allocation nor a call to
+/// [`Correctness::compute`], and gives the same answer as checking it against every
+/// [`Guess`] in the history with [`Guess::matches`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints {
+    /// The letter known to be at each position, if any.
+    green: [Option<u8>; 5],
+    /// Per position, a bitmask of letters known _not_ to be there.
+    not_at: [u32; 5],
+    /// The fewest times each letter can appear in the answer.
+    min: [u8; LETTERS],
+    /// The most times each letter can appear in the answer.
+    max: [u8; LETTERS],
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            green: [None; 5],
+            not_at: [0; 5],
+            min: [0; LETTERS],
+            max: [5; LETTERS],
+        }
+    }
+}
+
+impl Constraints {
+    pub fn from_history(history: &[Guess]) -> Self {
+        let mut this = Self::default();
+        for guess in history {
+            this.apply(guess);
+        }
+        this
+    }
+
+    /// Narrow the constraints with what `guess` revealed.
+    pub fn apply(&mut self, guess: &Guess) {
+        assert_eq!(guess.word.len(), 5);
+
+        // How many times each letter was colored, and whether any copy of it came back gray.
+        let mut seen = [0u8; LETTERS];
+        let mut capped = [false; LETTERS];
+        for (i, (g, &m)) in guess.word.bytes().zip(guess.mask.iter()).enumerate() {
+            let letter = usize::from(g - b'a');
+            match m {
+                Correctness::Correct => {
+                    self.green[i] = Some(g);
+                    seen[letter] += 1;
+                }
+                Correctness::Misplaced => {
+                    self.not_at[i] |= 1 << letter;
+                    seen[letter] += 1;
+                }
+                Correctness::Wrong => {
+                    self.not_at[i] |= 1 << letter;
+                    capped[letter] = true;
+                }
+            }
+        }
+
+        for letter in 0..LETTERS {
+            self.min[letter] = self.min[letter].max(seen[letter]);
+            if capped[letter] {
+                // A gray copy means there are no more of this letter than were colored.
+                self.max[letter] = self.max[letter].min(seen[letter]);
+            }
+        }
+    }
+
+    /// The letter known to be at `position`, if any.
+    pub fn green_at(&self, position: usize) -> Option<u8> {
+        self.green[position]
+    }
+
+    /// Whether `letter` is known not to be at `position`.
+    pub fn forbidden_at(&self, position: usize, letter: u8) -> bool {
+        self.not_at[position] & (1 << (letter - b'a')) != 0
+    }
+
+    /// The fewest times `letter` can appear in the answer.
+    pub fn min_count(&self, letter: u8) -> u8 {
+        self.min[usize::from(letter - b'a')]
+    }
+
+    /// The most times `letter` can appear in the answer.
+    pub fn max_count(&self, letter: u8) -> u8 {
+        self.max[usize::from(letter - b'a')]
+    }
+
+    /// Whether `word` could still be the answer.
+    pub fn permits(&self, word: &str) -> bool {
+        assert_eq!(word.len(), 5);
+
+        let mut counts = [0u8; LETTERS];
+        for (i, w) in word.bytes().enumerate() {
+            if self.green[i].is_some_and(|g| g != w) {
+                return false;
+            }
+            let letter = usize::from(w - b'a');
+            if self.not_at[i] & (1 << letter) != 0 {
+                return false;
+            }
+            counts[letter] += 1;
+        }
+
+        counts
+            .iter()
+            .zip(self.min.iter().zip(self.max.iter()))
+            .all(|(c, (min, max))| (min..=max).contains(&c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Constraints;
+    use crate::{Correctness, Guess, DICTIONARY};
+    use proptest::prelude::*;
+    use std::borrow::Cow;
+
+    fn history(answer: &str, guesses: &[&'static str]) -> Vec<Guess<'static>> {
+        guesses
+            .iter()
+            .map(|&word| Guess {
+                word: Cow::Borrowed(word),
+                mask: Correctness::compute(answer, word),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_matches_on_dictionary() {
+        for (answer, guesses) in [
+            ("right", &["trace", "sight"][..]),
+            ("aabbb", &["aaccc", "ccaac", "caacc"]),
+            ("hatch", &["match", "eerie"]),
+            ("geese", &["eerie", "sheen"]),
+        ] {
+            let history = history(answer, guesses);
+            let constraints = Constraints::from_history(&history);
+            for &(word, _) in &DICTIONARY {
+                assert_eq!(
+                    constraints.permits(word),
+                    history.iter().all(|g| g.matches(word)),
+                    "{} after {:?}",
+                    word,
+                    guesses
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn empty_history_allows_everything() {
+        let constraints = Constraints::from_history(&[]);
+        assert!(DICTIONARY.iter().all(|(word, _)| constraints.permits(word)));
+    }
+
+    fn word() -> impl Strategy<Value = &'static str> {
+        (0..DICTIONARY.len()).prop_map(|i| DICTIONARY[i].0)
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_matches_on_random_games(
+            answer in word(),
+            guesses in prop::collection::vec(word(), 1..=3),
+            words in prop::collection::vec(word(), 50),
+        ) {
+            let mut constraints = Constraints::default();
+            let mut history = Vec::new();
+            for word in guesses {
+                let guess = Guess {
+                    word: Cow::Borrowed(word),
+                    mask: Correctness::compute(answer, word),
+                };
+                constraints.apply(&guess);
+                history.push(guess);
+                prop_assert!(constraints.permits(answer));
+            }
+            for word in words {
+                prop_assert_eq!(
+                    constraints.permits(word),
+                    history.iter().all(|g| g.matches(word)),
+                    "{} after {:?}",
+                    word,
+                    history
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn accessors() {
+        let constraints = Constraints::from_history(&history("right", &["sight"]));
+        assert_eq!(constraints.green_at(0), None);
+        assert_eq!(constraints.green_at(1), Some(b'i'));
+        assert!(constraints.forbidden_at(0, b's'));
+        assert!(!constraints.forbidden_at(0, b'r'));
+        assert_eq!(constraints.min_count(b'g'), 1);
+        assert_eq!(constraints.max_count(b's'), 0);
+        assert_eq!(constraints.max_count(b'r'), 5);
+    }
+
+    #[test]
+    fn gray_caps_the_count() {
+        // `crept` has one `e`, so of the three in `eerie` one is yellow and the others gray,
+        // which says there is exactly one.
+        let history = history("crept", &["eerie"]);
+        assert_eq!(history[0].mask, mask![M W M W W]);
+        let constraints = Constraints::from_history(&history);
+        assert_eq!(constraints.min_count(b'e'), 1);
+        assert_eq!(constraints.max_count(b'e'), 1);
+
+        let mut filter = crate::Filter::default();
+        filter.observe(&history[0]);
+        let e_count = |word: &str| word.bytes().filter(|&b| b == b'e').count();
+        let mut excluded = 0;
+        for &(word, _) in &DICTIONARY {
+            if e_count(word) >= 2 {
+                assert!(!constraints.permits(word), "{}", word);
+                excluded += 1;
+            }
+        }
+        assert!(excluded > 0);
+        assert!(filter.iter().all(|word| e_count(word) == 1));
+        assert!(filter.iter().any(|word| word == "crept"));
+        // Would fit if only the minimum were kept.
+        assert!(!constraints.permits("freed"));
+    }
+}
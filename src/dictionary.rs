@@ -0,0 +1,350 @@
+use crate::{check_word, WordError};
+use std::{borrow::Cow, collections::HashMap, fmt, io::BufRead};
+
+/// The bundled dictionary, as `word frequency` lines.
+pub(crate) const DICTIONARY_TEXT: &str = include_str!("../dictionary.txt");
+
+/// The French dictionary, with accents, see [`Lang::Fr`].
+#[cfg(feature = "lang-fr")]
+pub(crate) const DICTIONARY_FR_TEXT: &str = include_str!("../dictionary_fr.txt");
+
+/// The German dictionary, with umlauts, see [`Lang::De`].
+#[cfg(feature = "lang-de")]
+pub(crate) const DICTIONARY_DE_TEXT: &str = include_str!("../dictionary_de.txt");
+
+/// The language of a bundled dictionary, see [`Wordle::new_lang`](crate::Wordle::new_lang).
+/// Only English is always there; the others are compiled in by their `lang-*` feature.
+///
+/// The game only knows the 26 ascii letters, so the French and German lists are stored as
+/// written and have their accents stripped as they are loaded: `é` and `ä` become `e` and `a`,
+/// and ligatures are spelled out (`œ` becomes `oe`). Words that end up the same, like `schön`
+/// and `schon`, become a single word whose frequency is the sum of theirs. Every bundled word
+/// is five letters once stripped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Lang {
+    #[default]
+    En,
+    #[cfg(feature = "lang-fr")]
+    Fr,
+    #[cfg(feature = "lang-de")]
+    De,
+}
+
+impl Lang {
+    /// The dictionary for this language as `word frequency` lines, stripped of accents, ready
+    /// for [`Wordle::from_dictionary`](crate::Wordle::from_dictionary) or
+    /// [`Options::with_dictionary`](crate::Options::with_dictionary).
+    ///
+    /// Where the English counts don't fit a `usize`, like on wasm32, they are scaled down as
+    /// in [`DICTIONARY`](crate::DICTIONARY).
+    pub fn dictionary(self) -> Cow<'static, str> {
+        match self {
+            Self::En => fit_counts(DICTIONARY_TEXT, usize::MAX as u64),
+            #[cfg(feature = "lang-fr")]
+            Self::Fr => Cow::Owned(strip_accents(DICTIONARY_FR_TEXT)),
+            #[cfg(feature = "lang-de")]
+            Self::De => Cow::Owned(strip_accents(DICTIONARY_DE_TEXT)),
+        }
+    }
+}
+
+/// `text` with every count divided by the same factor, so that none is above `max`, the way
+/// `build.rs` scales [`DICTIONARY`](crate::DICTIONARY). Counts stay at least one, and lines
+/// that aren't `word frequency` are kept as they are for the parser to report.
+pub(crate) fn fit_counts(text: &'static str, max: u64) -> Cow<'static, str> {
+    let count = |l: &str| l.split_once(' ')?.1.trim().parse::<u64>().ok();
+    let largest = lines(text).filter_map(|(_, l)| count(l)).max().unwrap_or(0);
+    let scale = largest / max + 1;
+    if scale == 1 {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    for (_, l) in lines(text) {
+        match (l.split_once(' '), count(l)) {
+            (Some((word, _)), Some(count)) => {
+                out.push_str(word);
+                out.push(' ');
+                out.push_str(&(count / scale).max(1).to_string());
+            }
+            _ => out.push_str(l),
+        }
+        out.push('\n');
+    }
+    Cow::Owned(out)
+}
+
+/// `text` with the accents taken off every word, merging the words that become the same, see
+/// [`Lang`]. Lines that aren't `word frequency` are kept as they are, ahead of the words, for
+/// the parser to report.
+#[cfg(any(feature = "lang-fr", feature = "lang-de"))]
+fn strip_accents(text: &str) -> String {
+    let mut words: Vec<(String, u64)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut out = String::new();
+    for (_, l) in lines(text) {
+        let Some((word, count)) = l.split_once(' ').and_then(|(w, c)| {
+            let count: u64 = c.trim().parse().ok()?;
+            Some((w, count))
+        }) else {
+            out.push_str(l);
+            out.push('\n');
+            continue;
+        };
+        let mut stripped = String::with_capacity(word.len());
+        for c in word.chars() {
+            match c {
+                'à' | 'â' | 'ä' => stripped.push('a'),
+                'ç' => stripped.push('c'),
+                'é' | 'è' | 'ê' | 'ë' => stripped.push('e'),
+                'î' | 'ï' => stripped.push('i'),
+                'ô' | 'ö' => stripped.push('o'),
+                'ù' | 'û' | 'ü' => stripped.push('u'),
+                'ÿ' => stripped.push('y'),
+                'œ' => stripped.push_str("oe"),
+                'æ' => stripped.push_str("ae"),
+                'ß' => stripped.push_str("ss"),
+                c => stripped.push(c),
+            }
+        }
+        match index.get(&stripped) {
+            Some(&i) => words[i].1 += count,
+            None => {
+                index.insert(stripped.clone(), words.len());
+                words.push((stripped, count));
+            }
+        }
+    }
+    for (word, count) in words {
+        out.push_str(&format!("{} {}\n", word, count));
+    }
+    out
+}
+
+/// Why a dictionary could not be loaded. Lines are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryError {
+    /// The line has a word but no frequency after it.
+    MissingFrequency { line: usize },
+    /// The word is not as long as expected.
+    WrongLength {
+        line: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// The character at `index` of the word (counting characters from 0) is not a lowercase
+    /// ascii letter, see [`check_word`].
+    NotLowercase {
+        line: usize,
+        index: usize,
+        found: char,
+    },
+    /// The frequency is not a non-negative integer.
+    InvalidFrequency { line: usize },
+    /// The line could not be read, for example because it is not valid UTF-8.
+    Unreadable { line: usize },
+}
+
+impl DictionaryError {
+    /// The line the problem was found on.
+    pub fn line(&self) -> usize {
+        match *self {
+            Self::MissingFrequency { line }
+            | Self::WrongLength { line, .. }
+            | Self::NotLowercase { line, .. }
+            | Self::InvalidFrequency { line }
+            | Self::Unreadable { line } => line,
+        }
+    }
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFrequency { line } => write!(f, "line {}: missing frequency", line),
+            Self::WrongLength {
+                line,
+                expected,
+                got,
+            } => write!(
+                f,
+                "line {}: word has {} letters, expected {}",
+                line, got, expected
+            ),
+            Self::NotLowercase { line, index, found } => write!(
+                f,
+                "line {}: '{}' at position {} is not a lowercase letter",
+                line, found, index
+            ),
+            Self::InvalidFrequency { line } => write!(f, "line {}: invalid frequency", line),
+            Self::Unreadable { line } => write!(f, "line {}: could not be read", line),
+        }
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+/// Parse a dictionary of `word frequency` lines, like the bundled one, for words of `N` letters.
+///
+/// Blank lines are skipped. Any word of a different length, or with anything but lowercase
+/// ascii letters, is rejected.
+pub fn parse_dictionary<const N: usize>(
+    text: &str,
+) -> Result<Vec<([u8; N], usize)>, DictionaryError> {
+    Ok(parse(text, N)?
+        .into_iter()
+        .map(|(word, count)| (word.as_bytes().try_into().unwrap(), count))
+        .collect())
+}
+
+/// Read all of `reader`, keeping line numbers intact for error reports.
+pub(crate) fn read(reader: impl BufRead) -> Result<String, DictionaryError> {
+    let mut text = String::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|_| DictionaryError::Unreadable { line: i + 1 })?;
+        text.push_str(&line);
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Parse `word frequency` lines, skipping blank ones, stopping at the first bad line.
+pub(crate) fn parse(text: &str, len: usize) -> Result<Vec<(&str, usize)>, DictionaryError> {
+    lines(text)
+        .map(|(line, l)| parse_line(line, l, len))
+        .collect()
+}
+
+/// The non-blank lines of `text`, with their line numbers.
+fn lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty())
+}
+
+fn parse_line(line: usize, l: &str, len: usize) -> Result<(&str, usize), DictionaryError> {
+    let (word, count) = l
+        .split_once(' ')
+        .ok_or(DictionaryError::MissingFrequency { line })?;
+    check_word(word, len).map_err(|e| match e {
+        WordError::WrongLength { expected, got } => DictionaryError::WrongLength {
+            line,
+            expected,
+            got,
+        },
+        WordError::NotLowercase { index, found } => {
+            DictionaryError::NotLowercase { line, index, found }
+        }
+    })?;
+    let count = count
+        .trim()
+        .parse()
+        .map_err(|_| DictionaryError::InvalidFrequency { line })?;
+    Ok((word, count))
+}
+
+/// How two dictionaries differ, see [`diff_dictionaries`]. Every list is in alphabetical order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DictDiff<'a> {
+    /// Words only in the first dictionary.
+    pub only_a: Vec<&'a str>,
+    /// Words only in the second dictionary.
+    pub only_b: Vec<&'a str>,
+    /// Words in both, with their frequency in the first and in the second.
+    pub common: Vec<(&'a str, usize, usize)>,
+}
+
+impl DictDiff<'_> {
+    /// Whether every word of the second dictionary is also in the first.
+    pub fn a_contains_b(&self) -> bool {
+        self.only_b.is_empty()
+    }
+
+    /// The common words whose frequency changed, with how much it went up (or down) from the
+    /// first dictionary to the second.
+    pub fn frequency_changes(&self) -> impl Iterator<Item = (&str, i128)> {
+        self.common
+            .iter()
+            .filter(|&&(_, a, b)| a != b)
+            .map(|&(word, a, b)| (word, b as i128 - a as i128))
+    }
+}
+
+/// Compare two dictionaries of five-letter `word frequency` lines, parsed like
+/// [`Wordle::from_dictionary`](crate::Wordle::from_dictionary) parses them. A word listed more
+/// than once counts with its last frequency.
+///
+/// Fails on the first malformed line, in `a` and then in `b`.
+pub fn diff_dictionaries<'a>(a: &'a str, b: &'a str) -> Result<DictDiff<'a>, DictionaryError> {
+    let a: HashMap<&str, usize> = parse(a, 5)?.into_iter().collect();
+    let b: HashMap<&str, usize> = parse(b, 5)?.into_iter().collect();
+
+    let mut diff = DictDiff::default();
+    for (&word, &count) in &a {
+        match b.get(word) {
+            Some(&other) => diff.common.push((word, count, other)),
+            None => diff.only_a.push(word),
+        }
+    }
+    diff.only_b = b.keys().filter(|w| !a.contains_key(*w)).copied().collect();
+    diff.only_a.sort_unstable();
+    diff.only_b.sort_unstable();
+    diff.common.sort_unstable();
+    Ok(diff)
+}
+
+/// A problem found by [`validate_dictionary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryIssue {
+    /// The line can't be loaded at all.
+    Malformed(DictionaryError),
+    /// The word was already on an earlier line, `first`.
+    Duplicate { line: usize, first: usize },
+}
+
+impl DictionaryIssue {
+    /// The line the problem was found on.
+    pub fn line(&self) -> usize {
+        match *self {
+            Self::Malformed(e) => e.line(),
+            Self::Duplicate { line, .. } => line,
+        }
+    }
+}
+
+impl fmt::Display for DictionaryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(e) => e.fmt(f),
+            Self::Duplicate { line, first } => {
+                write!(f, "line {}: word already on line {}", line, first)
+            }
+        }
+    }
+}
+
+/// Check a dictionary of five-letter `word frequency` lines, like the one
+/// [`Wordle::from_dictionary`](crate::Wordle::from_dictionary) loads, and report every problem
+/// rather than only the first.
+///
+/// Each line gets at most one [`DictionaryIssue::Malformed`], for the same problem loading it
+/// would report. Words that appear more than once are reported on every line after the first.
+/// An empty result means the dictionary loads as is.
+pub fn validate_dictionary(text: &str) -> Vec<DictionaryIssue> {
+    let mut first = HashMap::new();
+    let mut issues = Vec::new();
+    for (line, l) in lines(text) {
+        match parse_line(line, l, 5) {
+            Ok((word, _)) => {
+                if let Some(&first) = first.get(word) {
+                    issues.push(DictionaryIssue::Duplicate { line, first });
+                } else {
+                    first.insert(word, line);
+                }
+            }
+            Err(e) => issues.push(DictionaryIssue::Malformed(e)),
+        }
+    }
+    issues
+}
@@ -0,0 +1,78 @@
+use crate::{Guess, DICTIONARY};
+
+/// The words still consistent with every guess seen so far, narrowed down one guess at a time.
+///
+/// This is for guessers that would rather not keep the whole history around: each
+/// [`Filter::observe`] only checks the words that are left, so the state is the candidate list
+/// and nothing else. The candidates stay in the order they were given in.
+#[derive(Debug, Clone)]
+pub struct Filter {
+    candidates: Vec<&'static str>,
+}
+
+impl Default for Filter {
+    /// Every word in the bundled dictionary, most common first.
+    fn default() -> Self {
+        Self::new(DICTIONARY.iter().map(|&(word, _)| word))
+    }
+}
+
+impl Filter {
+    /// Start from `words`, before any guesses.
+    pub fn new(words: impl IntoIterator<Item = &'static str>) -> Self {
+        Self {
+            candidates: words.into_iter().collect(),
+        }
+    }
+
+    /// Drop every candidate that `guess` rules out.
+    pub fn observe(&mut self, guess: &Guess) {
+        let guess = guess.prepare();
+        self.candidates.retain(|word| guess.allows(word));
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.candidates.iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Filter;
+    use crate::{remaining_candidates, Correctness, Guess, DICTIONARY};
+    use std::borrow::Cow;
+
+    #[test]
+    fn agrees_with_whole_history() {
+        for (answer, guesses) in [
+            ("right", &["trace", "sight", "fight"][..]),
+            ("hatch", &["trace", "match", "patch", "latch"]),
+            ("geese", &["eerie", "sheen"]),
+        ] {
+            let mut filter = Filter::default();
+            let mut history = Vec::new();
+            for &word in guesses {
+                let guess = Guess {
+                    word: Cow::Borrowed(word),
+                    mask: Correctness::compute(answer, word),
+                };
+                filter.observe(&guess);
+                history.push(guess);
+
+                let words = DICTIONARY.iter().map(|&(word, _)| word);
+                let expected = remaining_candidates(words, &history);
+                assert_eq!(filter.iter().collect::<Vec<_>>(), expected);
+                assert_eq!(filter.len(), expected.len());
+            }
+            assert!(filter.iter().any(|word| word == answer));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{Guesser, Wordle};
+use std::{fmt::Write, time::Duration, time::Instant};
+
+/// How one guesser did over a set of games, see [`Wordle::evaluate`] and [`Leaderboard`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Summary {
+    pub games: usize,
+    /// Games that ran out of guesses.
+    pub failures: usize,
+    /// Mean guesses over the solved games, or `0.0` if none were solved.
+    pub mean: f64,
+    /// Median guesses over the solved games.
+    pub median: usize,
+    /// 99th percentile (nearest rank) of guesses over the solved games.
+    pub p99: usize,
+    /// `histogram[i]` is the number of games solved in `i + 1` guesses. It is as long as the
+    /// highest score.
+    pub histogram: Vec<usize>,
+    pub elapsed: Duration,
+}
+
+impl Summary {
+    pub fn new(scores: &[Option<usize>], elapsed: Duration) -> Self {
+        let mut histogram = Vec::new();
+        for &score in scores.iter().flatten() {
+            if histogram.len() < score {
+                histogram.resize(score, 0);
+            }
+            histogram[score - 1] += 1;
+        }
+        let solved: usize = histogram.iter().sum();
+        Self::from_histogram(histogram, scores.len() - solved, elapsed)
+    }
+
+    /// The summary over the games of both `self` and `other`, the same as [`Summary::new`]
+    /// gives for all their scores together, taking as long as both did. Everything is worked
+    /// out again from the histogram rather than averaged, so merging in any order or grouping
+    /// gives exactly the same result.
+    pub fn merge(&self, other: &Self) -> Self {
+        let (long, short) = if self.histogram.len() >= other.histogram.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut histogram = long.histogram.clone();
+        for (count, more) in histogram.iter_mut().zip(&short.histogram) {
+            *count += more;
+        }
+        Self::from_histogram(
+            histogram,
+            self.failures + other.failures,
+            self.elapsed + other.elapsed,
+        )
+    }
+
+    fn from_histogram(histogram: Vec<usize>, failures: usize, elapsed: Duration) -> Self {
+        let solved: usize = histogram.iter().sum();
+        let total: usize = histogram.iter().enumerate().map(|(i, n)| (i + 1) * n).sum();
+        // The score of the game at nearest rank `p`, going through the histogram in order.
+        let rank = |p: f64| {
+            let idx = (p * solved as f64).ceil() as usize;
+            let mut seen = 0;
+            histogram
+                .iter()
+                .position(|&n| {
+                    seen += n;
+                    seen >= idx.max(1)
+                })
+                .map_or(0, |i| i + 1)
+        };
+        Self {
+            games: solved + failures,
+            failures,
+            mean: if solved == 0 {
+                0.0
+            } else {
+                total as f64 / solved as f64
+            },
+            median: rank(0.5),
+            p99: rank(0.99),
+            histogram,
+            elapsed,
+        }
+    }
+}
+
+/// A side-by-side comparison of guessers, best first: fewest failures, then lowest mean.
+#[derive(Debug, Clone, Default)]
+pub struct Leaderboard {
+    entries: Vec<(String, Summary)>,
+}
+
+impl Leaderboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, name: impl Into<String>, summary: Summary) {
+        self.entries.push((name.into(), summary));
+    }
+
+    /// Play every one of `answers` with a fresh guesser from `make_guesser`, and record how it
+    /// went under `name`.
+    pub fn compete<G: Guesser>(
+        &mut self,
+        name: impl Into<String>,
+        wordle: &Wordle,
+        answers: &[&'static str],
+        mut make_guesser: impl FnMut() -> G,
+    ) {
+        let start = Instant::now();
+        let scores: Vec<_> = answers
+            .iter()
+            .map(|&answer| wordle.play(answer, make_guesser()))
+            .collect();
+        self.record(name, Summary::new(&scores, start.elapsed()));
+    }
+
+    fn ranked(&self) -> Vec<[String; 6]> {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries
+            .sort_by(|(_, a), (_, b)| a.failures.cmp(&b.failures).then(a.mean.total_cmp(&b.mean)));
+        entries
+            .into_iter()
+            .map(|(name, s)| {
+                [
+                    name.clone(),
+                    format!("{:.4}", s.mean),
+                    s.median.to_string(),
+                    s.p99.to_string(),
+                    s.failures.to_string(),
+                    format!("{:.2?}", s.elapsed),
+                ]
+            })
+            .collect()
+    }
+
+    /// Render as an aligned plain-text table.
+    pub fn to_table(&self) -> String {
+        let rows = self.ranked();
+        // Padding counts characters, and so must the widths: names and times like `µs` aren't
+        // always ascii.
+        let mut widths = HEADER.map(|cell| cell.chars().count());
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        let mut line = |cells: [&str; 6]| {
+            let mut l = String::new();
+            for (i, (cell, w)) in cells.iter().zip(widths).enumerate() {
+                if i == 0 {
+                    write!(l, "{:<w$}", cell, w = w).unwrap();
+                } else {
+                    write!(l, "  {:>w$}", cell, w = w).unwrap();
+                }
+            }
+            out.push_str(&l);
+            out.push('\n');
+        };
+        line(HEADER);
+        for row in &rows {
+            line(row.each_ref().map(String::as_str));
+        }
+        out
+    }
+
+    /// Render as a markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("| {} |\n", HEADER.join(" | "));
+        out.push_str("|:---|---:|---:|---:|---:|---:|\n");
+        for row in self.ranked() {
+            out.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+        out
+    }
+}
+
+const HEADER: [&str; 6] = ["guesser", "mean", "median", "p99", "failures", "time"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Leaderboard {
+        let mut board = Leaderboard::new();
+        board.record(
+            "slow",
+            Summary::new(&[Some(4), Some(5), None], Duration::from_millis(1500)),
+        );
+        board.record(
+            "fast",
+            Summary::new(&[Some(3), Some(4), Some(3)], Duration::from_millis(20)),
+        );
+        board
+    }
+
+    #[test]
+    fn summary() {
+        let s = Summary::new(&[Some(2), Some(6), None, Some(4), Some(3)], Duration::ZERO);
+        assert_eq!(s.games, 5);
+        assert_eq!(s.failures, 1);
+        assert_eq!(s.mean, 3.75);
+        assert_eq!(s.median, 3);
+        assert_eq!(s.p99, 6);
+    }
+
+    #[test]
+    fn histogram() {
+        let s = Summary::new(&[Some(2), Some(4), None, Some(4), Some(3)], Duration::ZERO);
+        assert_eq!(s.histogram, [0, 1, 1, 2]);
+        assert_eq!(s.failures, 1);
+        assert_eq!(s.mean, 3.25);
+        assert_eq!(
+            Summary::new(&[None], Duration::ZERO),
+            Summary {
+                games: 1,
+                failures: 1,
+                ..Summary::default()
+            }
+        );
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(
+            board().to_table(),
+            "\
+guesser    mean  median  p99  failures     time
+fast     3.3333       3    4         0  20.00ms
+slow     4.5000       4    5         1    1.50s
+"
+        );
+    }
+
+    #[test]
+    fn plain_lines_up_non_ascii() {
+        let mut board = board();
+        board.record("crème", Summary::new(&[Some(2)], Duration::from_micros(12)));
+        let table = board.to_table();
+        let widths: Vec<_> = table.lines().map(|l| l.chars().count()).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]), "{}", table);
+        assert!(table.contains("crème"));
+        assert!(table.contains("12.00µs"));
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            board().to_markdown(),
+            "\
+| guesser | mean | median | p99 | failures | time |
+|:---|---:|---:|---:|---:|---:|
+| fast | 3.3333 | 3 | 4 | 0 | 20.00ms |
+| slow | 4.5000 | 4 | 5 | 1 | 1.50s |
+"
+        );
+    }
+}
@@ -1,202 +1,43 @@
-use std::{borrow::Cow, collections::HashSet, num::NonZeroU8};
+//! Everything but the masks themselves needs `std`, which is on by default. Without it, only
+//! [`Correctness`], [`PackedCorrectness`] and [`Guess`] are left, for computing and matching
+//! masks on targets without an operating system (an allocator is still needed for [`Guess`]).
+#![cfg_attr(not(feature = "std"), no_std)]
 
-mod solver;
-pub use solver::{Rank, Solver};
-
-include!(concat!(env!("OUT_DIR"), "/dictionary.rs"));
-
-pub struct Wordle {
-    dictionary: HashSet<&'static str>,
-}
-
-impl Default for Wordle {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl Wordle {
-    pub fn new() -> Self {
-        Self {
-            dictionary: HashSet::from_iter(DICTIONARY.iter().copied().map(|(word, _)| word)),
-        }
-    }
-
-    pub fn play<G: Guesser>(&self, answer: &'static str, mut guesser: G) -> Option<usize> {
-        let mut history = Vec::new();
-
-        // Popoki allows at least 32 guesses.
-        for i in 1..=32 {
-            let guess = guesser.guess(&history);
-            if guess == answer {
-                guesser.finish(i);
-                return Some(i);
-            }
-            assert!(
-                self.dictionary.contains(&*guess),
-                "guess '{}' is not in the dictionary",
-                guess
-            );
-
-            let correctness = Correctness::compute(answer, &guess);
-            history.push(Guess {
-                word: Cow::Owned(guess),
-                mask: correctness,
-            });
-        }
-        None
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum Correctness {
-    // Green
-    Correct,
-    /// Yellow
-    Misplaced,
-    /// Gray
-    Wrong,
-}
-
-impl Correctness {
-    fn is_misplaced(letter: u8, answer: &str, used: &mut [bool; 5]) -> bool {
-        answer.bytes().enumerate().any(|(i, a)| {
-            if a == letter && !used[i] {
-                used[i] = true;
-                return true;
-            }
-            false
-        })
-    }
-
-    /// Given an answer and a guess, return an array of 5 elements, each of which is
-    /// a `Result` indicating whether the guess is correct, incorrect, or not present
-    pub fn compute(answer: &str, guess: &str) -> [Self; 5] {
-        // Sanity check for `answer` and `guess`
-        assert_eq!(answer.len(), 5);
-        assert_eq!(guess.len(), 5);
-
-        let mut c = [Correctness::Wrong; 5];
-        let answer_bytes = answer.as_bytes();
-        let guess_bytes = guess.as_bytes();
-
-        // Array indexed by lowercase ascii letters
-        let mut misplaced = [0u8; (b'z' - b'a' + 1) as usize];
-
-        // Find all correct letters
-        for ((&answer, &guess), c) in answer_bytes.iter().zip(guess_bytes).zip(c.iter_mut()) {
-            if answer == guess {
-                *c = Correctness::Correct
-            } else {
-                // If the letter does not match, count it as misplaced
-                misplaced[(answer - b'a') as usize] += 1;
-            }
-        }
+extern crate alloc;
 
-        // Check all of the non matching letters if they are misplaced
-        for (&guess, c) in guess_bytes.iter().zip(c.iter_mut()) {
-            // If the letter was guessed wrong and the same letter was counted as misplaced
-            if *c == Correctness::Wrong && misplaced[(guess - b'a') as usize] > 0 {
-                *c = Correctness::Misplaced;
-                misplaced[(guess - b'a') as usize] -= 1;
-            }
-        }
-        c
-    }
-}
-
-pub const MAX_MASK_ENUM: usize = 3 * 3 * 3 * 3 * 3;
-
-/// A wrapper type for `[Correctness; 5]` packed into a single byte with a niche.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-// The NonZeroUB here lets the compiler know that we're not using the value `0`,
-// and that `0` can therefore be used to represent `None` for `Option<PackedCorrectness>`
-pub struct PackedCorrectness(NonZeroU8);
-
-impl From<[Correctness; 5]> for PackedCorrectness {
-    fn from(c: [Correctness; 5]) -> Self {
-        let packed = c.iter().fold(0, |acc, c| {
-            acc * 3
-                + match c {
-                    Correctness::Correct => 0,
-                    Correctness::Misplaced => 1,
-                    Correctness::Wrong => 2,
-                }
-        });
-        Self(NonZeroU8::new(packed + 1).unwrap())
-    }
-}
-
-impl From<PackedCorrectness> for u8 {
-    fn from(this: PackedCorrectness) -> Self {
-        this.0.get() - 1
-    }
-}
-
-pub struct Guess<'a> {
-    pub word: Cow<'a, str>,
-    pub mask: [Correctness; 5],
-}
-
-impl Guess<'_> {
-    pub fn matches(&self, word: &str) -> bool {
-        assert_eq!(word.len(), 5);
-        assert_eq!(self.word.len(), 5);
-
-        let mut used = [false; 5];
-
-        // Check correct letters
-        for (i, (a, g)) in word.bytes().zip(self.word.bytes()).enumerate() {
-            if a == g {
-                if self.mask[i] != Correctness::Correct {
-                    return false;
-                }
-                used[i] = true;
-            } else if self.mask[i] == Correctness::Correct {
-                return false;
-            }
-        }
-
-        // Check misplaced letters
-        for (g, e) in self.word.bytes().zip(self.mask.iter()) {
-            if *e == Correctness::Correct {
-                continue;
-            }
-            if Correctness::is_misplaced(g, word, &mut used) != (*e == Correctness::Misplaced) {
-                return false;
-            }
-        }
-
-        // The rest will be all correctly wrong letters
-        true
-    }
-}
-
-pub trait Guesser {
-    fn guess(&mut self, history: &[Guess]) -> String;
-    fn finish(&self, _guesses: usize) {}
-}
-
-impl Guesser for fn(history: &[Guess]) -> String {
-    fn guess(&mut self, history: &[Guess]) -> String {
-        (*self)(history)
-    }
+/// A five-letter word literal, checked when compiling rather than with [`check_word`] at run
+/// time.
+///
+/// ```
+/// let word: &'static str = popoki::word!("trace");
+/// assert_eq!(word, "trace");
+/// ```
+///
+/// ```compile_fail
+/// popoki::word!("Trace");
+/// ```
+#[macro_export]
+macro_rules! word {
+    ($word:literal) => {{
+        const WORD: &str = $word;
+        const _: () = assert!($crate::is_word(WORD), "not a five-letter lowercase word");
+        WORD
+    }};
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 macro_rules! guesser {
     (|$history:ident| $impl:block) => {{
         struct G;
         impl $crate::Guesser for G {
-            fn guess(&mut self, $history: &[Guess]) -> String {
+            fn guess(&mut self, $history: &[$crate::Guess]) -> String {
                 $impl
             }
         }
         G
     }};
 }
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 macro_rules! mask {
     (C) => {$crate::Correctness::Correct};
     (M) => {$crate::Correctness::Misplaced};
@@ -206,8 +47,121 @@ macro_rules! mask {
     ]}
 }
 
-#[cfg(test)]
+#[cfg(feature = "std")]
+pub mod algorithms;
+#[cfg(feature = "compute-cache")]
+mod compute_cache;
+#[cfg(feature = "std")]
+mod constraints;
+#[cfg(feature = "std")]
+mod dictionary;
+#[cfg(feature = "std")]
+mod filter;
+#[cfg(feature = "std")]
+mod leaderboard;
+mod mask;
+#[cfg(feature = "std")]
+mod multi;
+#[cfg(feature = "std")]
+mod optimal;
+#[cfg(feature = "serde")]
+mod record;
+#[cfg(feature = "std")]
+mod share;
+#[cfg(feature = "std")]
+mod solver;
+#[cfg(feature = "std")]
+mod split;
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+mod wordle;
+#[cfg(feature = "compute-cache")]
+pub use compute_cache::ComputeCache;
+#[cfg(feature = "std")]
+pub use constraints::Constraints;
+#[cfg(feature = "std")]
+pub use dictionary::{
+    diff_dictionaries, parse_dictionary, validate_dictionary, DictDiff, DictionaryError,
+    DictionaryIssue, Lang,
+};
+#[cfg(feature = "std")]
+pub use filter::Filter;
+#[cfg(feature = "std")]
+pub use leaderboard::{Leaderboard, Summary};
+pub use mask::{
+    check_word, is_word, turn, ComputeMode, Correctness, Guess, LetterInfo, PackedCorrectness,
+    PreparedGuess, WordError, MAX_MASK_ENUM,
+};
+#[cfg(feature = "std")]
+pub use multi::MultiWordle;
+#[cfg(feature = "std")]
+pub use optimal::{expected_optimal_guesses, guess_regret, MAX_EXACT};
+#[cfg(feature = "serde")]
+pub use record::GuessRecord;
+#[cfg(feature = "std")]
+pub use share::{
+    parse_share_grid, render_game, render_mask, share_text, GridParseError, ParseMaskError, Squares,
+};
+#[cfg(feature = "std")]
+pub use solver::{
+    MaskScorer, Options, Rank, Solver, UnknownFallback, UnknownOpener, UnknownOpening, WordCounts,
+    BUDGET,
+};
+#[cfg(feature = "std")]
+pub use split::{
+    best_complementary_opener, best_opener_by, partition, second_guess_table, Entropy,
+    ExpectedSize, SplitCriterion, WorstCase,
+};
+#[cfg(feature = "std")]
+pub use timeout::TimeoutGuesser;
+#[cfg(feature = "std")]
+pub use wordle::{
+    rejected_by, remaining_candidates, CoverageReport, GameGrade, GuessError, GuessOutcome,
+    Guesser, GuesserExt, HardModeViolation, PlayError, PlayResult, PlayTrace, TurnGrade, TurnInfo,
+    Wordle, HARD_ANSWERS,
+};
+
+#[cfg(feature = "std")]
+include!(concat!(env!("OUT_DIR"), "/dictionary.rs"));
+
+/// SplitMix64: advance `state` and mix it into the next output. Any seed is fine, including
+/// `0`, and consecutive seeds give unrelated first outputs.
+#[cfg(feature = "std")]
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
+    use crate::{Guesser, Summary, Wordle};
+    use std::time::Duration;
+
+    /// The words `guesser` plays on the bundled dictionary until it finds `answer` or runs out
+    /// of guesses.
+    fn played(guesser: impl Guesser, answer: &'static str) -> Vec<String> {
+        Wordle::new()
+            .play_recording(answer, guesser)
+            .into_history()
+            .into_iter()
+            .map(|guess| guess.word.into_owned())
+            .collect()
+    }
+
+    /// `summary` without the time it took, for comparing two runs of the same games.
+    fn untimed(summary: Summary) -> Summary {
+        Summary {
+            elapsed: Duration::ZERO,
+            ..summary
+        }
+    }
+
     mod guess_matcher {
         use crate::Guess;
         use std::borrow::Cow;
@@ -218,6 +172,10 @@ mod tests {
                     word: Cow::Borrowed($prev),
                     mask: mask![$($mask )+]
                 }.matches($next));
+                assert!(Guess {
+                    word: Cow::Borrowed($prev),
+                    mask: mask![$($mask )+]
+                }.allows($next));
                 assert_eq!($crate::Correctness::compute($next, $prev), mask![$($mask )+]);
             };
             ($prev:literal + [$($mask:tt)+] disallows $next:literal) => {
@@ -225,6 +183,10 @@ mod tests {
                     word: Cow::Borrowed($prev),
                     mask: mask![$($mask )+]
                 }.matches($next));
+                assert!(!Guess {
+                    word: Cow::Borrowed($prev),
+                    mask: mask![$($mask )+]
+                }.allows($next));
                 assert_ne!($crate::Correctness::compute($next, $prev), mask![$($mask )+]);
             }
         }
@@ -243,9 +205,57 @@ mod tests {
 
             check!("tares" + [W M M W W] disallows "brink");
         }
+
+        #[test]
+        fn allows_agrees_with_matches() {
+            let words: Vec<_> = crate::DICTIONARY.iter().map(|&(w, _)| w).collect();
+            for guess in ["trace", "abbey", "eerie", "mamma"] {
+                for answer in words.iter().step_by(101) {
+                    let g = Guess {
+                        word: Cow::Borrowed(guess),
+                        mask: crate::Correctness::compute(answer, guess),
+                    };
+                    let prepared = g.prepare();
+                    for word in &words {
+                        assert_eq!(g.allows(word), g.matches(word), "{:?} vs {}", g, word);
+                        assert_eq!(
+                            prepared.allows(word),
+                            g.matches(word),
+                            "{:?} vs {}",
+                            g,
+                            word
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn prepared_agrees_on_every_mask() {
+            // Including masks no answer can give, like a gray `e` before a yellow one.
+            let words: Vec<_> = crate::DICTIONARY.iter().map(|&(w, _)| w).collect();
+            for guess in ["eerie", "geese"] {
+                for mask in crate::Correctness::patterns() {
+                    let g = Guess {
+                        word: Cow::Borrowed(guess),
+                        mask,
+                    };
+                    let prepared = g.prepare();
+                    for word in words.iter().step_by(7) {
+                        assert_eq!(
+                            prepared.allows(word),
+                            g.matches(word),
+                            "{:?} vs {}",
+                            g,
+                            word
+                        );
+                    }
+                }
+            }
+        }
     }
     mod game {
-        use crate::{Guess, Wordle};
+        use crate::Wordle;
         #[test]
         fn genius() {
             let w = Wordle::new();
@@ -320,52 +330,2566 @@ mod tests {
             assert_eq!(w.play("right", guesser), None);
         }
     }
-    mod compute {
-        use crate::Correctness;
+    mod candidates {
+        use crate::{remaining_candidates, Correctness, Guess, Wordle, DICTIONARY};
+        use proptest::prelude::*;
+        use std::{borrow::Cow, sync::OnceLock};
+
+        fn guess(word: &'static str, answer: &str) -> Guess<'static> {
+            Guess {
+                word: Cow::Borrowed(word),
+                mask: Correctness::compute(answer, word),
+            }
+        }
 
         #[test]
-        fn all_green() {
-            assert_eq!(Correctness::compute("abcde", "abcde"), mask![C C C C C]);
+        fn narrows_to_one() {
+            let w = Wordle::from_dictionary("right 1\nsight 1\nnight 1\nfight 1\nwrong 1").unwrap();
+            assert_eq!(w.candidates(&[]).len(), 5);
+
+            let history = [guess("sight", "night")];
+            assert_eq!(w.candidates(&history), ["fight", "night", "right"]);
+            let history = [guess("sight", "night"), guess("wrong", "night")];
+            assert_eq!(w.candidates(&history), ["night"]);
+        }
+
+        fn wordle() -> &'static Wordle {
+            static WORDLE: OnceLock<Wordle> = OnceLock::new();
+            WORDLE.get_or_init(Wordle::new)
+        }
+
+        fn word() -> impl Strategy<Value = &'static str> {
+            (0..DICTIONARY.len()).prop_map(|i| DICTIONARY[i].0)
         }
 
         #[test]
-        fn all_gray() {
-            assert_eq!(Correctness::compute("abcde", "fghij"), mask![W W W W W]);
+        fn estimate_without_history() {
+            assert_eq!(wordle().estimate_remaining(&[]), wordle().len());
+        }
+
+        proptest! {
+            // Every case filters the whole dictionary three times.
+            #![proptest_config(ProptestConfig::with_cases(32))]
+
+            #[test]
+            fn estimate_is_exact(answer in word(), guesses in prop::collection::vec(word(), 3)) {
+                let mut history = Vec::new();
+                for word in guesses {
+                    history.push(guess(word, answer));
+                    prop_assert_eq!(
+                        wordle().estimate_remaining(&history),
+                        wordle().candidates(&history).len(),
+                        "{:?}",
+                        history
+                    );
+                }
+            }
         }
 
         #[test]
-        fn all_yellow() {
-            assert_eq!(Correctness::compute("abcde", "eabcd"), mask![M M M M M]);
+        fn keeps_order() {
+            let dict = ["sight", "wrong", "night", "right"];
+            let history = [guess("trace", "night")];
+            assert_eq!(remaining_candidates(dict, &history), ["sight", "night"]);
         }
+    }
+    mod lists {
+        use crate::{Guess, Guesser, Solver, Wordle};
+
+        const GUESSABLE: &str = "right 5\nsight 4\nnight 3\nfight 2\nlight 1";
+        const ANSWERS: &str = "night 1\nfight 1";
 
         #[test]
-        fn repeat_green() {
-            assert_eq!(Correctness::compute("aabbb", "aaccc"), mask![C C W W W]);
+        fn guessable_words_are_not_candidates() {
+            let w = Wordle::with_lists(GUESSABLE, ANSWERS).unwrap();
+            assert_eq!(w.candidates(&[]), ["fight", "night"]);
+
+            let mut words = vec!["night", "right"];
+            let score = w.play("night", |_: &[Guess]| words.pop().unwrap().to_string());
+            assert_eq!(score, Some(2));
         }
 
         #[test]
-        fn repeat_yellow() {
-            assert_eq!(Correctness::compute("aabbb", "ccaac"), mask![W W M M W]);
+        fn solver_only_picks_answers() {
+            let w = Wordle::with_lists(GUESSABLE, ANSWERS).unwrap();
+            let options = Solver::builder().with_lists(GUESSABLE, ANSWERS).unwrap();
+            assert_eq!(options.clone().build().remaining(), Some(2));
+
+            for answer in ["night", "fight"] {
+                let outcome = w.play_recording(answer, options.clone().build());
+                assert!(matches!(outcome.score(), Some(1..=2)), "{}", answer);
+                for guess in outcome.history() {
+                    assert!(["night", "fight"].contains(&&*guess.word), "{}", guess.word);
+                }
+            }
         }
 
         #[test]
-        fn repeat_some_green() {
-            assert_eq!(Correctness::compute("aabbb", "caacc"), mask![W C M W W]);
+        #[cfg(feature = "compute-cache")]
+        fn lists_keep_the_compute_cache() {
+            let cached = Solver::builder().with_lists(GUESSABLE, ANSWERS).unwrap();
+            assert_eq!(cached.compute_cache.as_ref().map(|c| c.len()), Some(5));
+            let mut uncached = Solver::builder();
+            uncached.cache = false;
+            let uncached = uncached.with_lists(GUESSABLE, ANSWERS).unwrap();
+
+            let w = Wordle::with_lists(GUESSABLE, ANSWERS).unwrap();
+            for answer in ["night", "fight"] {
+                assert_eq!(
+                    w.play_recording(answer, cached.clone().build()),
+                    w.play_recording(answer, uncached.clone().build())
+                );
+            }
+        }
+    }
+    mod rejected {
+        use crate::{rejected_by, Correctness, Guess, Guesser, Solver};
+        use std::borrow::Cow;
+
+        #[test]
+        fn rejected_by_finds_first_offender() {
+            let history = [
+                Guess {
+                    word: Cow::Borrowed("abcde"),
+                    mask: mask![W W W W W],
+                },
+                Guess {
+                    word: Cow::Borrowed("fghij"),
+                    mask: mask![C W W W W],
+                },
+            ];
+            assert_eq!(rejected_by(&history, "fklmn"), None);
+            assert_eq!(rejected_by(&history, "aklmn"), Some(0));
+            assert_eq!(rejected_by(&history, "klmno"), Some(1));
         }
 
         #[test]
-        fn only_one_yellow() {
-            assert_eq!(Correctness::compute("azzaz", "aaabb"), mask![C M W W W]);
+        fn solver_logs_rejections() {
+            let mut solver = Solver::builder();
+            solver.log_rejected = true;
+            let mut solver = solver.build();
+
+            let mut history = Vec::new();
+            for _ in 0..2 {
+                let guess = solver.guess(&history);
+                let mask = Correctness::compute("right", &guess);
+                history.push(Guess {
+                    word: Cow::Owned(guess),
+                    mask,
+                });
+            }
+            solver.guess(&history);
+
+            let rejected = solver.rejected().unwrap();
+            assert!(!rejected.is_empty());
+            assert!(!rejected.iter().any(|&(word, _)| word == "right"));
+            for &(word, by) in &rejected {
+                assert_eq!(rejected_by(&history, word), Some(by), "{}", word);
+            }
         }
 
         #[test]
-        fn only_one_green() {
-            assert_eq!(Correctness::compute("baccc", "aaddd"), mask![W C W W W]);
+        fn solver_does_not_log_by_default() {
+            let mut solver = Solver::default();
+            let guess = solver.guess(&[]);
+            solver.guess(&[Guess {
+                word: Cow::Owned(guess),
+                mask: mask![W W W W W],
+            }]);
+            assert!(solver.rejected().is_none());
         }
+    }
+    mod progress {
+        use crate::{Correctness, Guess, Guesser, Solver};
+        use std::borrow::Cow;
 
         #[test]
-        fn only_one_gray() {
-            assert_eq!(Correctness::compute("abcde", "aacde"), mask![C W C C C]);
+        fn reports_scan_progress() {
+            let easy = || {
+                let mut solver = Solver::builder();
+                solver.hard_mode = false;
+                #[cfg(feature = "parallel")]
+                {
+                    solver.parallel = false;
+                }
+                solver.build()
+            };
+            let mut solver = easy();
+            let guess = solver.guess(&[]);
+            let history = [Guess {
+                mask: Correctness::compute("right", &guess),
+                word: Cow::Owned(guess),
+            }];
+
+            let mut calls = Vec::new();
+            let guess =
+                solver.guess_with_progress(&history, |done, total| calls.push((done, total)));
+
+            let total = calls.last().unwrap().1;
+            assert!(calls.len() > 1);
+            assert_eq!(calls.last(), Some(&(total, total)));
+            assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+
+            let mut plain = easy();
+            plain.guess(&[]);
+            assert_eq!(plain.guess(&history), guess);
+        }
+
+        #[test]
+        fn within_budget() {
+            use std::time::Duration;
+
+            for hard_mode in [true, false] {
+                let mut options = Solver::builder();
+                options.hard_mode = hard_mode;
+                options.opener = None;
+                let solver = options.build();
+
+                // Nothing is known yet, so every word would be scored without the budget.
+                let guess = solver.fresh().guess_within(&[], Duration::ZERO);
+                assert!(crate::DICTIONARY.iter().any(|&(w, _)| w == guess));
+
+                let history = [Guess {
+                    mask: Correctness::compute("right", "trace"),
+                    word: Cow::Borrowed("trace"),
+                }];
+                let guess = solver.fresh().guess_within(&history, Duration::ZERO);
+                assert!(crate::DICTIONARY.iter().any(|&(w, _)| w == guess));
+                if hard_mode {
+                    assert!(history[0].matches(&guess), "{}", guess);
+                }
+
+                let unhurried = solver
+                    .fresh()
+                    .guess_within(&history, Duration::from_secs(3600));
+                assert_eq!(unhurried, solver.fresh().guess(&history));
+            }
+        }
+    }
+    mod rank {
+        use crate::{Correctness, Guess, Guesser, Solver};
+        use std::borrow::Cow;
+
+        #[test]
+        fn best_first_and_matches_guess() {
+            let mut solver = Solver::default();
+            let guess = solver.guess(&[]);
+            let history = [Guess {
+                mask: Correctness::compute("hatch", &guess),
+                word: Cow::Owned(guess),
+            }];
+
+            let ranked = solver.rank(&history);
+            assert!(ranked.len() > 1);
+            assert!(ranked.windows(2).all(|w| w[0].1 >= w[1].1));
+            // Ranking leaves the solver as it was.
+            assert_eq!(solver.rank(&history), ranked);
+            let best = ranked[0].0.to_string();
+            assert_eq!(solver.guess(&history), best);
+        }
+    }
+    mod best_split {
+        use crate::{Correctness, Guess, Guesser, Solver};
+        use std::borrow::Cow;
+
+        #[test]
+        fn probe_beats_candidates() {
+            let mut solver = Solver::builder()
+                .with_lists(
+                    "films 1\n",
+                    "fight 6\nlight 5\nmight 4\nnight 3\nsight 2\nright 1\n",
+                )
+                .unwrap()
+                .build();
+            // Any candidate only tells itself apart from the other five. `films` separates
+            // everything but `night` and `right`.
+            let (word, share) = solver.best_split(&[]);
+            assert_eq!(word, "films");
+            assert_eq!(share, 2.0 / 6.0);
+            assert_ne!(solver.guess(&[]), "films");
+
+            let history = [Guess {
+                word: Cow::Borrowed("films"),
+                mask: Correctness::compute("night", "films"),
+            }];
+            let (word, share) = solver.best_split(&history);
+            assert_eq!(share, 0.5);
+            // Either candidate tells the pair apart, which the probe no longer can.
+            assert!(["night", "right"].contains(&word));
+        }
+    }
+    mod expected_guesses {
+        use crate::{Guess, Solver};
+
+        #[test]
+        fn splitting_beats_hoping() {
+            let mut options = Solver::builder()
+                .with_dictionary("hatch 1\nmatch 1\npatch 1\nlatch 1\nbatch 1\nplumb 1\n")
+                .unwrap();
+            let answers = ["hatch", "match", "patch", "latch", "batch"];
+            options.answers = Some(answers.into_iter().map(Box::from).collect());
+            let solver = options.build();
+            let history: [Guess; 0] = [];
+
+            // `plumb` can't win, but tells every candidate apart, so the answer comes next.
+            assert_eq!(solver.expected_guesses("plumb", &history), 2.0);
+            // `hatch` wins a fifth of the time, and otherwise leaves four words that only differ
+            // in their first letter, which no single candidate can sort out.
+            let hatch = solver.expected_guesses("hatch", &history);
+            assert!(hatch > 2.5, "{}", hatch);
+        }
+
+        #[test]
+        fn one_candidate_left() {
+            let solver = Solver::builder()
+                .with_dictionary("hatch 1\n")
+                .unwrap()
+                .build();
+            assert_eq!(solver.expected_guesses("hatch", &[]), 1.0);
+        }
+    }
+    mod tally {
+        use crate::{Correctness, Guess, Rank, Solver};
+        use std::borrow::Cow;
+
+        /// Expected information the slow way: for every pattern, find the candidates that
+        /// would give it.
+        fn naive_entropy(word: &str, candidates: &[(&str, usize)]) -> f64 {
+            let total: usize = candidates.iter().map(|&(_, c)| c).sum();
+            let mut entropy = 0.0;
+            for mask in Correctness::patterns() {
+                let g = Guess {
+                    word: Cow::Borrowed(word),
+                    mask,
+                };
+                let count: usize = candidates
+                    .iter()
+                    .filter(|&&(c, _)| g.matches(c))
+                    .map(|&(_, c)| c)
+                    .sum();
+                if count != 0 {
+                    let p = count as f64 / total as f64;
+                    entropy -= p * p.log2();
+                }
+            }
+            entropy
+        }
+
+        #[test]
+        fn one_compute_per_candidate_agrees_with_every_pattern() {
+            let text: String = crate::DICTIONARY
+                .iter()
+                .step_by(250)
+                .map(|&(w, c)| format!("{} {}\n", w, c))
+                .collect();
+            let mut options = Solver::builder().with_dictionary(&text).unwrap();
+            options.rank_by = Rank::ExpectedInformation;
+            options.hard_mode = false;
+            options.cutoff = false;
+            options.sigmoid = false;
+            let solver = options.clone().build();
+            let dictionary = options.dictionary.unwrap();
+            let words: Vec<(&str, usize)> = dictionary.iter().map(|(w, c)| (&**w, *c)).collect();
+
+            let first = words[0].0;
+            let answer = words[words.len() / 2].0;
+            let history = [Guess {
+                word: Cow::Borrowed(first),
+                mask: Correctness::compute(answer, first),
+            }];
+            for history in [&[][..], &history] {
+                let candidates: Vec<_> = words
+                    .iter()
+                    .copied()
+                    .filter(|&(w, _)| history.iter().all(|g| g.matches(w)))
+                    .collect();
+                let ranked = solver.rank(history);
+                // Words already played aren't scored again.
+                assert_eq!(ranked.len(), words.len() - history.len());
+                for (word, goodness) in ranked {
+                    let naive = naive_entropy(word, &candidates);
+                    assert!(
+                        (goodness - naive).abs() < 1e-9,
+                        "{} {} {}",
+                        word,
+                        goodness,
+                        naive
+                    );
+                }
+            }
+        }
+
+        /// Pinned games, so that a change to how the entropy sum is taken that moves a single
+        /// guess is noticed.
+        #[test]
+        fn entropy_sum_plays_the_same() {
+            let games = [
+                ("rebut", "trace semen house rebut"),
+                ("humph", "trace lions dumpy humph"),
+                ("blush", "trace lions slump flush blush"),
+                ("evade", "trace slump dwang evade"),
+            ];
+            let w = crate::Wordle::new();
+            for (answer, expected) in games {
+                let mut options = Solver::builder();
+                options.hard_mode = false;
+                let history = w.play_recording(answer, options.build()).into_history();
+                let words: Vec<_> = history.iter().map(|g| &*g.word).collect();
+                assert_eq!(words.join(" "), expected);
+            }
+        }
+    }
+    mod endgame {
+        use crate::{guess_regret, Guesser, Rank, Solver};
+
+        #[test]
+        fn beats_greedy_entropy() {
+            const REMAINING: [&str; 3] = ["aaaaa", "aaaab", "aaabb"];
+            // Every word here splits the three candidates apart, so entropy alone can't tell
+            // them apart and the common probe `bbbbb` wins the tie, though it can never win
+            // outright.
+            let solver = |endgame| {
+                let mut options = Solver::builder()
+                    .with_dictionary("bbbbb 1000\naaaaa 1\naaaab 1\naaabb 1\n")
+                    .unwrap();
+                options.answers = Some(REMAINING.into_iter().map(Box::from).collect());
+                options.rank_by = Rank::ExpectedInformation;
+                options.hard_mode = false;
+                options.endgame = endgame;
+                options.build()
+            };
+            assert_eq!(solver(None).guess(&[]), "bbbbb");
+            assert_eq!(solver(Some(2)).guess(&[]), "bbbbb");
+
+            let guess = solver(Some(10)).guess(&[]);
+            assert_ne!(guess, "bbbbb");
+            assert_eq!(guess_regret(&guess, &REMAINING, &["bbbbb"]), 0.0);
+            assert!(guess_regret("bbbbb", &REMAINING, &["bbbbb"]) > 0.0);
+        }
+
+        #[test]
+        fn plays_well() {
+            let w = crate::Wordle::new();
+            let mut options = Solver::builder();
+            options.endgame = Some(6);
+            let answers = ["cigar", "hatch", "sissy", "awake"];
+            let stats = w.evaluate(|| options.clone().build(), &answers);
+            assert_eq!(stats.failures, 0);
+        }
+    }
+    mod solve_bonus {
+        use crate::{Guesser, Rank, Solver};
+
+        #[test]
+        fn prefers_a_possible_answer() {
+            // `mouth` tells `hatch` and `match` apart just as well as either of them does, and
+            // wins the tie on frequency unless the chance of solving counts for something.
+            let guesser = |solve_bonus| {
+                let mut options = Solver::builder()
+                    .with_lists("mouth 1000\n", "hatch 1\nmatch 1\n")
+                    .unwrap();
+                options.rank_by = Rank::ExpectedInformation;
+                options.hard_mode = false;
+                options.solve_bonus = solve_bonus;
+                options.build()
+            };
+            assert_eq!(guesser(0.0).guess(&[]), "mouth");
+            assert_eq!(guesser(0.1).guess(&[]), "hatch");
+        }
+    }
+    mod excluded_letters {
+        use crate::{Guess, Guesser, Solver};
+
+        fn q_and_z() -> [bool; 26] {
+            let mut letters = [false; 26];
+            letters[usize::from(b'q' - b'a')] = true;
+            letters[usize::from(b'z' - b'a')] = true;
+            letters
+        }
+
+        #[test]
+        fn never_guessed() {
+            for hard_mode in [true, false] {
+                let mut options = Solver::builder().with_excluded_letters(q_and_z());
+                options.hard_mode = hard_mode;
+                let mut solver = options.build();
+                for answer in [
+                    "cigar", "humph", "squad", "ghost", "quite", "gauze", "water",
+                ] {
+                    solver.reset();
+                    let guesses = super::played(|h: &[Guess]| solver.guess(h), answer);
+                    assert_eq!(guesses.last().map(|w| &**w), Some(answer));
+                    if answer.contains(['q', 'z']) {
+                        // The answer itself has to be played in the end.
+                        assert!(solver.used_excluded_letters());
+                    } else {
+                        for guess in &guesses {
+                            assert!(!guess.contains(['q', 'z']), "{} {:?}", answer, guesses);
+                        }
+                        assert!(!solver.used_excluded_letters());
+                    }
+                }
+            }
+        }
+    }
+    mod aesthetic {
+        use crate::{Correctness, Guess, Guesser, Solver, Wordle};
+        use std::borrow::Cow;
+
+        fn yellows(mask: &[Correctness; 5]) -> i32 {
+            mask.iter()
+                .filter(|&&c| c == Correctness::Misplaced)
+                .count() as i32
+        }
+
+        #[test]
+        fn picks_among_near_best() {
+            let history = [Guess {
+                word: Cow::Borrowed("trace"),
+                mask: Correctness::compute("fight", "trace"),
+            }];
+            let plain = Solver::default().guess(&history);
+            let strict = Solver::builder()
+                .with_aesthetic(0.0, yellows)
+                .build()
+                .guess(&history);
+            assert_eq!(strict, plain);
+
+            let pretty = Solver::builder()
+                .with_aesthetic(f64::INFINITY, yellows)
+                .build()
+                .guess(&history);
+            assert_ne!(pretty, plain);
+            // Still a word that could be the answer, as hard mode requires.
+            assert!(history[0].matches(&pretty));
+        }
+
+        #[test]
+        fn stays_within_epsilon() {
+            let history = [Guess {
+                word: Cow::Borrowed("trace"),
+                mask: Correctness::compute("fight", "trace"),
+            }];
+            let plain = Solver::default();
+            let ranked = plain.rank(&history);
+            let best = ranked[0].1;
+            let mut picked = Vec::new();
+            for epsilon in [0.01, 0.1, 0.5] {
+                let pretty = Solver::builder()
+                    .with_aesthetic(epsilon, yellows)
+                    .build()
+                    .guess(&history);
+                let &(_, goodness) = ranked.iter().find(|(w, _)| *w == pretty).unwrap();
+                assert!(
+                    goodness >= best - epsilon,
+                    "{} {} {}",
+                    epsilon,
+                    pretty,
+                    goodness
+                );
+                picked.push(pretty);
+            }
+            // A wider margin does let it pick a prettier, worse word.
+            assert!(picked.iter().any(|w| w != ranked[0].0), "{:?}", picked);
+        }
+
+        #[test]
+        fn still_solves() {
+            let w = Wordle::new();
+            for answer in ["fight", "humph", "cigar"] {
+                let solver = Solver::builder()
+                    .with_aesthetic(f64::INFINITY, yellows)
+                    .build();
+                assert!(w.play(answer, solver).is_some(), "{}", answer);
+            }
+        }
+    }
+    mod repeats {
+        use crate::{Correctness, Guess, Guesser, Rank, Solver};
+        use std::borrow::Cow;
+
+        #[test]
+        fn never_replays_a_guess() {
+            // Penalizing possible answers this hard makes `crane`, which can't be the answer
+            // any more and tells nothing, score better than either word that can.
+            let mut options = Solver::builder()
+                .with_dictionary("crane 1000\nfight 1\nsight 1\n")
+                .unwrap();
+            options.rank_by = Rank::ExpectedScore;
+            options.hard_mode = false;
+            options.solve_bonus = -10.0;
+            options.opener = None;
+            let history = [Guess {
+                word: Cow::Borrowed("crane"),
+                mask: Correctness::compute("fight", "crane"),
+            }];
+            let mut solver = options.clone().build();
+            assert_eq!(solver.rank(&history).len(), 2);
+            assert!(["fight", "sight"].contains(&&*solver.guess(&history)));
+
+            options.endgame = Some(2);
+            let mut solver = options.build();
+            assert!(["fight", "sight"].contains(&&*solver.guess(&history)));
+        }
+    }
+    mod opener {
+        use crate::{Correctness, Guess, Guesser, Solver, UnknownOpener};
+        use std::borrow::Cow;
+
+        const TEXT: &str = "right 50\nsight 40\nnight 30\nfight 20\nwrong 10\nmight 5\n";
+
+        #[test]
+        fn stable() {
+            let mut solver = Solver::builder().with_dictionary(TEXT).unwrap().build();
+            let best = solver.best_opener().to_string();
+            assert_eq!(solver.best_opener(), best);
+
+            let guess = solver.guess(&[]);
+            solver.guess(&[Guess {
+                mask: Correctness::compute("wrong", &guess),
+                word: Cow::Owned(guess),
+            }]);
+            assert_eq!(solver.best_opener(), best);
+            let fresh = Solver::builder().with_dictionary(TEXT).unwrap().build();
+            assert_eq!(fresh.best_opener(), best);
+        }
+
+        #[test]
+        fn scores_every_word() {
+            // With the cutoff, the opening scan stops after about a third of the dictionary.
+            let solver = Solver::builder().build();
+            let mut exhaustive = Solver::builder();
+            exhaustive.cutoff = false;
+            let exhaustive = exhaustive.build();
+            let ranked = exhaustive.rank(&[]);
+            assert_eq!(ranked.len(), crate::DICTIONARY.len());
+            assert_eq!(solver.best_opener(), ranked[0].0);
+        }
+
+        #[test]
+        fn forcing_it_changes_nothing() {
+            let mut scored = Solver::builder().with_dictionary(TEXT).unwrap();
+            scored.opener = None;
+            let mut forced = scored.clone();
+            // The opener has to outlive the options, so take it from the dictionary text.
+            let best = scored.clone().build().best_opener().to_string();
+            forced.opener = TEXT.split_whitespace().find(|&word| word == best);
+
+            for answer in ["right", "sight", "night", "fight", "wrong", "might"] {
+                let played = super::played(scored.clone().build(), answer);
+                assert_eq!(played[0], forced.opener.unwrap());
+                assert_eq!(played, super::played(forced.clone().build(), answer));
+            }
+        }
+
+        #[test]
+        fn with_opener() {
+            let mut solver = Solver::builder().with_opener("slate").unwrap().build();
+            assert_eq!(solver.guess(&[]), "slate");
+
+            let custom = Solver::builder().with_dictionary(TEXT).unwrap();
+            assert_eq!(
+                custom.clone().with_opener("slate").err(),
+                Some(UnknownOpener { opener: "slate" })
+            );
+            assert!(custom.with_opener("might").is_ok());
+        }
+    }
+    mod fallback {
+        use crate::{Solver, UnknownFallback};
+
+        // None of these is `cigar`, or could be.
+        const TEXT: &str = "right 50\nsight 40\nnight 30\nfight 20\nwrong 10\nmight 5\n";
+
+        fn words(options: crate::Options) -> Vec<String> {
+            super::played(options.build(), "cigar")
+        }
+
+        #[test]
+        fn answer_not_in_the_dictionary() {
+            let played = words(Solver::builder().with_dictionary(TEXT).unwrap());
+            // The game runs out of turns rather than panicking, on words the dictionary has.
+            assert_eq!(played.len(), 32);
+            assert!(played.iter().all(|w| TEXT.contains(w.as_str())));
+            // Each word is played once, and then the first one over and over.
+            let mut distinct = played[..6].to_vec();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), 6, "{:?}", played);
+            assert!(played[6..].iter().all(|w| w == "right"), "{:?}", played);
+        }
+
+        #[test]
+        fn with_fallback() {
+            let custom = Solver::builder().with_dictionary(TEXT).unwrap();
+            assert_eq!(
+                custom.clone().with_fallback("cigar").err(),
+                Some(UnknownFallback { fallback: "cigar" })
+            );
+            let played = words(custom.with_fallback("might").unwrap());
+            assert_eq!(played.iter().filter(|w| *w == "might").count(), 1);
+            assert_eq!(played.len(), 32);
+        }
+    }
+    mod sigmoid {
+        use crate::{Correctness, Guess, Solver};
+        use std::borrow::Cow;
+
+        #[test]
+        fn prefers_common_words() {
+            // Both split each other perfectly, so only their weights tell them apart.
+            let history = [Guess {
+                word: Cow::Borrowed("trace"),
+                mask: Correctness::compute("truce", "trace"),
+            }];
+            for steepness in [30_000_000.0, 1_000.0] {
+                let mut solver = Solver::builder();
+                solver.sigmoid_steepness = steepness;
+                let solver = solver.build();
+                let ranked = solver.rank(&history);
+                assert_eq!(ranked.len(), 2);
+                assert_eq!(ranked[0].0, "truce");
+                assert_eq!(ranked[1].0, "trice");
+                assert!(ranked[0].1 > ranked[1].1, "{:?}", ranked);
+            }
+        }
+    }
+    #[cfg(feature = "parallel")]
+    mod parallel {
+        use crate::Solver;
+
+        fn game(answer: &'static str, parallel: bool, hard_mode: bool) -> Vec<String> {
+            let mut solver = Solver::builder();
+            solver.parallel = parallel;
+            solver.hard_mode = hard_mode;
+            super::played(solver.build(), answer)
+        }
+
+        #[test]
+        fn same_guesses_as_sequential() {
+            for answer in ["right", "hatch", "abbey"] {
+                for hard_mode in [true, false] {
+                    assert_eq!(
+                        game(answer, true, hard_mode),
+                        game(answer, false, hard_mode),
+                        "{} (hard mode: {})",
+                        answer,
+                        hard_mode
+                    );
+                }
+            }
+        }
+    }
+    mod anagrams {
+        use crate::Solver;
+
+        fn options(dedup_anagrams: bool) -> crate::Options {
+            let mut options = Solver::builder();
+            // Hard mode only ever scores candidates, which are never collapsed.
+            options.hard_mode = false;
+            options.dedup_anagrams = dedup_anagrams;
+            options
+        }
+
+        #[test]
+        fn dedup_keeps_choices() {
+            let mut collapsed = false;
+            for answer in [
+                "heath", "karma", "stink", "cigar", "humph", "fight", "awake", "rebut", "sissy",
+                "watch",
+            ] {
+                let plain = super::played(options(false).build(), answer);
+                let dedup = super::played(options(true).build(), answer);
+                assert_eq!(plain, dedup);
+
+                let history = crate::Wordle::new()
+                    .play_recording(answer, options(false).build())
+                    .into_history();
+                let scored = |dedup| options(dedup).build().rank(&history[..1]).len();
+                collapsed |= scored(true) < scored(false);
+            }
+            // Make sure there actually were anagrams to collapse after the opener.
+            assert!(collapsed);
+        }
+    }
+    mod optimal {
+        use crate::Wordle;
+
+        #[test]
+        fn tiny_dictionary() {
+            let w = Wordle::from_dictionary("aaaaa 1\naaaab 1\naaabb 1").unwrap();
+            assert!((w.optimal_mean_guesses() - 5.0 / 3.0).abs() < 1e-9);
+        }
+
+        #[test]
+        #[should_panic]
+        fn bundled_dictionary_is_too_large() {
+            Wordle::new().optimal_mean_guesses();
+        }
+    }
+    mod grade {
+        use crate::Wordle;
+
+        #[test]
+        fn by_hand() {
+            let w = Wordle::from_dictionary("aaaaa 1\nbbbbb 1\nccccc 1\nabcxx 1").unwrap();
+            let grade = w.grade("ccccc", &["aaaaa", "bbbbb", "ccccc"]);
+            let turns: Vec<_> = grade
+                .turns
+                .iter()
+                .map(|t| (&*t.guess, t.candidates, t.bits, t.rank, &*t.best))
+                .collect();
+            assert_eq!(
+                turns,
+                [
+                    // `abcxx` tells all four apart. Each of the others leaves two words
+                    // together, so they share second place.
+                    ("aaaaa", 4, 1.5, 2, "abcxx"),
+                    // Left with `bbbbb` and `ccccc`, guessing either one (or `abcxx`) tells
+                    // them apart, and only the first two could win outright.
+                    ("bbbbb", 2, 1.0, 1, "bbbbb"),
+                    ("ccccc", 1, 0.0, 1, "ccccc"),
+                ]
+            );
+        }
+    }
+    mod daily {
+        use crate::Wordle;
+
+        #[test]
+        fn stable() {
+            let a = Wordle::new();
+            let b = Wordle::new();
+            assert_eq!(a.daily(0), b.daily(0));
+            let days: Vec<_> = (0..50).map(|day| a.daily(day)).collect();
+            assert!(days.iter().all(|word| a.contains(word)));
+            assert_eq!(days, (0..50).map(|day| b.daily(day)).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn only_answers() {
+            let lists = Wordle::with_lists(
+                "right 5\nsight 4\nwrong 3\n",
+                "eight 1\nfight 1\nlight 1\nmight 1\nnight 1\ntight 1\n",
+            )
+            .unwrap();
+            let days: Vec<_> = (0..6).map(|day| lists.daily(day)).collect();
+            // Pinned, so that a change to the mapping is noticed.
+            assert_eq!(days, ["fight", "tight", "night", "might", "night", "light"]);
+        }
+    }
+    mod hard_answers {
+        use crate::{algorithms::Random, Wordle, DICTIONARY, HARD_ANSWERS};
+        use std::collections::HashSet;
+
+        #[test]
+        fn in_the_dictionary() {
+            let words: HashSet<&str> = DICTIONARY.iter().map(|&(w, _)| w).collect();
+            let answers: HashSet<&str> = include_str!("../answers.txt").lines().collect();
+            let mut seen = HashSet::new();
+            for &word in HARD_ANSWERS {
+                assert!(words.contains(word), "{}", word);
+                assert!(answers.contains(word), "{}", word);
+                assert!(seen.insert(word), "{} is listed twice", word);
+            }
+        }
+
+        #[test]
+        fn evaluate_hard() {
+            let stats = Wordle::new().evaluate_hard(|| Random::new(1));
+            let games = stats.histogram.iter().sum::<usize>() + stats.failures;
+            assert_eq!(games, HARD_ANSWERS.len());
+        }
+    }
+    mod information_gained {
+        use crate::{Correctness, Guess, Wordle};
+        use std::borrow::Cow;
+
+        #[test]
+        fn by_hand() {
+            let w = Wordle::from_dictionary(
+                "right 1\nsight 1\nnight 1\nfight 1\nmight 1\nlight 1\nwrong 1\nhumph 1\n",
+            )
+            .unwrap();
+            let guess = |word| Guess {
+                word: Cow::Borrowed(word),
+                mask: Correctness::compute("fight", word),
+            };
+            // `humph` leaves the five `-ight` words without an m, 8 to 5 candidates. `sight`
+            // then rules out only itself, 5 to 4.
+            let bits = w.information_gained(&[guess("humph"), guess("sight")]);
+            assert_eq!(bits.len(), 2);
+            assert!((bits[0] - (8f64.log2() - 5f64.log2())).abs() < 1e-12);
+            assert!((bits[1] - (5f64.log2() - 4f64.log2())).abs() < 1e-12);
+            assert!(w.information_gained(&[]).is_empty());
+        }
+    }
+    mod hint {
+        use crate::{Correctness, Guess, Wordle};
+        use std::borrow::Cow;
+
+        #[test]
+        fn keeps_greens_in_place() {
+            let w = Wordle::new();
+            for answer in ["cigar", "rebut", "sissy", "awake"] {
+                let history = [Guess {
+                    word: Cow::Borrowed("crane"),
+                    mask: Correctness::compute(answer, "crane"),
+                }];
+                let hint = w.hint(answer, &history).unwrap();
+                assert_ne!(hint, "crane");
+                assert!(history[0].matches(&hint), "{} {}", answer, hint);
+                for (i, c) in history[0].mask.iter().enumerate() {
+                    if *c == Correctness::Correct {
+                        assert_eq!(hint.as_bytes()[i], b"crane"[i], "{} {}", answer, hint);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn by_hand() {
+            let w = Wordle::from_dictionary("aaaaa 1\nbbbbb 1\nccccc 1\nabcxx 1").unwrap();
+            // `abcxx` would tell the rest apart, but the gray `a`s rule it out.
+            let history = [Guess {
+                word: Cow::Borrowed("aaaaa"),
+                mask: Correctness::compute("ccccc", "aaaaa"),
+            }];
+            assert_eq!(w.hint("ccccc", &history).as_deref(), Some("bbbbb"));
+        }
+
+        #[test]
+        fn none_without_a_legal_word() {
+            let w = Wordle::from_dictionary("aaaaa 1\nabbbb 1").unwrap();
+            let history = [Guess {
+                word: Cow::Borrowed("aaaaa"),
+                mask: Correctness::compute("ccccc", "aaaaa"),
+            }];
+            assert_eq!(w.hint("ccccc", &history), None);
+        }
+    }
+    mod adversarial {
+        use crate::{Guess, Solver, Wordle};
+
+        #[test]
+        fn forces_every_word() {
+            // Every guess rules out only itself, so the host can hold out until the last one.
+            let w = Wordle::from_dictionary("fight 1\nlight 1\nmight 1\nnight 1\nright 1").unwrap();
+            let first_candidate = |history: &[Guess]| w.candidates(history)[0].to_string();
+            assert_eq!(w.play_adversarial(first_candidate), 5);
+            assert_eq!(w.play("fight", first_candidate), Some(1));
+        }
+
+        #[test]
+        fn harder_than_a_fixed_answer() {
+            let w = Wordle::new();
+            let adversarial = w.play_adversarial(Solver::default());
+            let fixed = w.play("cigar", Solver::default()).unwrap();
+            assert!(adversarial > fixed, "{} vs {}", adversarial, fixed);
+        }
+    }
+    mod check_guess {
+        use crate::{GuessError, Wordle};
+
+        #[test]
+        fn each_error() {
+            let w = Wordle::new();
+            assert_eq!(w.check_guess("crane"), Ok("crane"));
+            assert_eq!(w.check_guess(" CRANE\n"), Ok("crane"));
+            assert_eq!(
+                w.check_guess("cranes"),
+                Err(GuessError::WrongLength { got: 6 })
+            );
+            assert_eq!(w.check_guess(""), Err(GuessError::WrongLength { got: 0 }));
+            assert_eq!(
+                w.check_guess("écran"),
+                Err(GuessError::NonAscii {
+                    index: 0,
+                    found: 'é'
+                })
+            );
+            assert_eq!(w.check_guess("zzzzz"), Err(GuessError::NotInDictionary));
+            assert_eq!(w.check_guess("cr4ne"), Err(GuessError::NotInDictionary));
+        }
+    }
+    mod play_result {
+        use crate::{Guess, PlayResult, Solver, Wordle};
+
+        #[test]
+        fn each_ending() {
+            let w = Wordle::new();
+            assert!(matches!(
+                w.play_result("cigar", Solver::default()),
+                PlayResult::Solved(n) if n <= 6
+            ));
+            assert_eq!(
+                w.play_result("cigar", |_: &[Guess]| "right".to_string()),
+                PlayResult::Exhausted
+            );
+            // `right`, and then a word that isn't one.
+            let mut guesses = ["zzzzz", "right"];
+            assert_eq!(
+                w.play_result("cigar", |_: &[Guess]| {
+                    guesses.rotate_left(1);
+                    guesses[0].to_string()
+                }),
+                PlayResult::InvalidGuess("zzzzz".to_string())
+            );
+        }
+
+        #[test]
+        fn wrong_length() {
+            let w = Wordle::new();
+            for guess in ["hi", "hello!"] {
+                assert_eq!(
+                    w.play_result("cigar", |_: &[Guess]| guess.to_string()),
+                    PlayResult::InvalidGuess(guess.to_string())
+                );
+            }
+        }
+    }
+    mod unknown_answer {
+        use crate::{Guess, PlayError, Wordle};
+
+        #[test]
+        fn reported_without_guessing() {
+            let w = Wordle::new();
+            let guesser = |_: &[Guess]| -> String { panic!("should not be asked") };
+            assert_eq!(
+                w.try_play("zzzzz", guesser),
+                Err(PlayError::AnswerNotInDictionary { answer: "zzzzz" })
+            );
+            assert_eq!(
+                w.try_play("right", |_: &[Guess]| "right".to_string()),
+                Ok(Some(1))
+            );
+        }
+
+        #[test]
+        fn play_runs_out_of_guesses() {
+            let w = Wordle::new();
+            assert_eq!(w.play("zzzzz", |_: &[Guess]| "right".to_string()), None);
+        }
+    }
+    mod limit {
+        use crate::{Guess, Guesser, Wordle};
+
+        /// Guesses wrong until `right_on`, counting how often it was asked.
+        struct Counter {
+            right_on: usize,
+            asked: usize,
+        }
+
+        impl Guesser for &mut Counter {
+            fn guess(&mut self, _history: &[Guess]) -> String {
+                self.asked += 1;
+                if self.asked == self.right_on {
+                    "right".to_string()
+                } else {
+                    "wrong".to_string()
+                }
+            }
+        }
+
+        #[test]
+        fn gives_up_at_limit() {
+            let w = Wordle::new();
+            for limit in [1, 6, 10] {
+                let mut c = Counter {
+                    right_on: usize::MAX,
+                    asked: 0,
+                };
+                assert_eq!(w.play_with_limit("right", &mut c, limit), None);
+                assert_eq!(c.asked, limit);
+            }
+        }
+
+        #[test]
+        fn strict_rules() {
+            let w = Wordle::new();
+            let mut c = Counter {
+                right_on: 7,
+                asked: 0,
+            };
+            assert_eq!(w.play_with_limit("right", &mut c, 6), None);
+            let mut c = Counter {
+                right_on: 7,
+                asked: 0,
+            };
+            assert_eq!(w.play("right", &mut c), Some(7));
+        }
+
+        #[test]
+        fn zero() {
+            let w = Wordle::new();
+            let mut c = Counter {
+                right_on: 1,
+                asked: 0,
+            };
+            assert_eq!(w.play_with_limit("right", &mut c, 0), None);
+            assert_eq!(c.asked, 0);
+        }
+    }
+    mod closures {
+        use crate::{Guess, Wordle};
+
+        #[test]
+        fn captures_state() {
+            let w = Wordle::new();
+            let mut words = vec!["right", "sight", "wrong"];
+            let guesser = |_history: &[Guess]| words.pop().unwrap().to_string();
+            assert_eq!(w.play("right", guesser), Some(3));
+            assert!(words.is_empty());
+        }
+
+        #[test]
+        fn fn_pointer() {
+            fn right(_history: &[Guess]) -> String {
+                "right".to_string()
+            }
+            let guesser: fn(&[Guess]) -> String = right;
+            assert_eq!(Wordle::new().play("right", guesser), Some(1));
+        }
+    }
+    mod boxed {
+        use crate::{algorithms::Random, Guess, Guesser, GuesserExt, Solver, Wordle};
+
+        #[test]
+        fn plays_like_the_unboxed() {
+            let w = Wordle::new();
+            let mut guessers: Vec<Box<dyn Guesser>> = vec![
+                Solver::default().boxed(),
+                Random::new(7).boxed(),
+                (|_history: &[Guess]| "cigar".to_string()).boxed(),
+            ];
+            assert_eq!(
+                w.play("humph", guessers.remove(0)),
+                w.play("humph", Solver::default())
+            );
+            assert_eq!(
+                w.play_recording("humph", guessers.remove(0)),
+                w.play_recording("humph", Random::new(7))
+            );
+            assert_eq!(w.play("cigar", guessers.remove(0)), Some(1));
+        }
+
+        #[test]
+        fn reset_reaches_the_guesser() {
+            let w = Wordle::new();
+            let mut g = Random::new(7).boxed();
+            let answers = ["cigar", "cigar"];
+            let stats = w.evaluate_reusing(&mut g, &answers);
+            assert_eq!(
+                super::untimed(stats),
+                super::untimed(w.evaluate(|| Random::new(7), &answers))
+            );
+        }
+    }
+    mod hard_mode {
+        use crate::{HardModeViolation, Wordle};
+
+        #[test]
+        fn ignoring_greens_is_rejected() {
+            let w = Wordle::new();
+            // "sight" leaves only the first letter to find, but "wrong" throws the greens away.
+            let guesser = guesser!(|history| {
+                ["sight", "fight", "wrong", "night"][history.len()].to_string()
+            });
+            assert_eq!(
+                w.play_hard_mode("night", guesser),
+                Err(HardModeViolation {
+                    turn: 3,
+                    guess: "wrong".to_string()
+                })
+            );
+        }
+
+        #[test]
+        fn respecting_clues_plays_through() {
+            let w = Wordle::new();
+            let guesser = guesser!(|history| {
+                ["sight", "fight", "light", "night"][history.len()].to_string()
+            });
+            assert_eq!(w.play_hard_mode("night", guesser), Ok(Some(4)));
+        }
+
+        #[test]
+        fn exploring_fits_every_clue() {
+            let w = Wordle::new();
+            for &answer in crate::HARD_ANSWERS {
+                let solver = crate::Solver::builder().with_exploration(0).build();
+                assert!(
+                    w.play_hard_mode(answer, solver).unwrap().is_some(),
+                    "{}",
+                    answer
+                );
+            }
+        }
+    }
+    mod evaluate {
+        use crate::Wordle;
+
+        #[test]
+        fn fresh_guesser_per_answer() {
+            let w = Wordle::new();
+            // Each guesser cycles through the same words, starting over only when it is new.
+            let make = || {
+                let mut words = ["wrong", "right", "night", "sight"];
+                move |_history: &[crate::Guess]| {
+                    words.rotate_left(1);
+                    words[words.len() - 1].to_string()
+                }
+            };
+            let stats = w.evaluate(make, &["wrong", "right", "night", "tight"]);
+            assert_eq!(stats.histogram, [1, 1, 1]);
+            assert_eq!(stats.failures, 1);
+            assert_eq!(stats.mean, 2.0);
+        }
+
+        #[test]
+        fn merge_in_any_order() {
+            use crate::Summary;
+            use std::time::Duration;
+
+            let scores = [
+                Some(3),
+                None,
+                Some(1),
+                Some(7),
+                Some(3),
+                None,
+                Some(4),
+                Some(2),
+            ];
+            let parts: Vec<_> = scores
+                .chunks(3)
+                .map(|chunk| Summary::new(chunk, Duration::from_millis(chunk.len() as u64)))
+                .collect();
+            let whole = Summary::new(&scores, Duration::from_millis(scores.len() as u64));
+            assert_eq!((whole.median, whole.p99), (3, 7));
+            let (a, b, c) = (&parts[0], &parts[1], &parts[2]);
+            assert_eq!(a.merge(b).merge(c), whole);
+            assert_eq!(a.merge(&b.merge(c)), whole);
+            assert_eq!(c.merge(a).merge(b), whole);
+            assert_eq!(whole.merge(&Summary::default()), whole);
+        }
+
+        #[cfg(feature = "parallel")]
+        #[test]
+        fn parallel_same_as_sequential() {
+            use crate::Solver;
+            use std::sync::Mutex;
+
+            let w = Wordle::new();
+            let answers = [
+                "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "jazzy",
+            ];
+            let calls = Mutex::new(Vec::new());
+            let parallel = w.evaluate_parallel(Solver::default, &answers, |done, total| {
+                calls.lock().unwrap().push((done, total))
+            });
+            assert_eq!(
+                super::untimed(parallel),
+                super::untimed(w.evaluate(Solver::default, &answers))
+            );
+
+            let mut calls = calls.into_inner().unwrap();
+            calls.sort_unstable();
+            let expected: Vec<_> = (1..=answers.len())
+                .map(|done| (done, answers.len()))
+                .collect();
+            assert_eq!(calls, expected);
+        }
+    }
+    mod games {
+        use crate::{Solver, Wordle};
+        use itertools::Itertools;
+
+        #[test]
+        fn same_as_playing_each() {
+            let w = Wordle::new();
+            let answers = ["cigar", "rebut", "sissy", "humph"];
+            let streamed: Vec<_> = w.games(&answers, Solver::default).collect();
+            let played: Vec<_> = answers
+                .iter()
+                .map(|&answer| (answer, w.play(answer, Solver::default())))
+                .collect();
+            assert_eq!(streamed, played);
+
+            // Nothing is played until asked for.
+            let mut made = 0;
+            let mut games = w.games(&answers, || {
+                made += 1;
+                Solver::default()
+            });
+            games.next();
+            drop(games);
+            assert_eq!(made, 1);
+
+            let counts = w
+                .games(&answers, Solver::default)
+                .counts_by(|(_, score)| score);
+            assert_eq!(counts.values().sum::<usize>(), answers.len());
+        }
+    }
+    mod reset {
+        use crate::{Correctness, Guess, Guesser, Solver, Wordle};
+
+        #[test]
+        fn solver_plays_again_after_reset() {
+            let w = Wordle::new();
+            let mut solver = Solver::default();
+            let all = solver.remaining();
+
+            let mut history = Vec::new();
+            for _ in 0..2 {
+                let guess = solver.guess(&history);
+                history.push(Guess {
+                    word: guess.clone().into(),
+                    mask: Correctness::compute("cigar", &guess),
+                });
+            }
+            assert!(solver.remaining() < all);
+
+            solver.reset();
+            assert_eq!(solver.remaining(), all);
+            let stats = w.evaluate_reusing(&mut solver, &["cigar", "humph"]);
+            assert_eq!(stats.failures, 0);
+        }
+
+        #[test]
+        fn fresh_ignores_progress() {
+            let w = Wordle::new();
+            let mut solver = Solver::default();
+            let guess = solver.guess(&[]);
+            solver.guess(&[Guess {
+                mask: Correctness::compute("humph", &guess),
+                word: guess.into(),
+            }]);
+
+            let fresh = solver.fresh();
+            assert_eq!(fresh.remaining(), Solver::default().remaining());
+            for answer in ["cigar", "humph", "sissy"] {
+                assert_eq!(
+                    w.play_recording(answer, solver.fresh()),
+                    w.play_recording(answer, Solver::default())
+                );
+            }
+        }
+
+        #[test]
+        fn clone_keeps_progress() {
+            let mut solver = Solver::default();
+            let mut history = Vec::new();
+            for _ in 0..2 {
+                let guess = solver.guess(&history);
+                history.push(Guess {
+                    mask: Correctness::compute("sissy", &guess),
+                    word: guess.into(),
+                });
+            }
+            solver.guess(&history);
+
+            let mut copy = solver.clone();
+            assert_eq!(copy.remaining(), solver.remaining());
+            assert!(copy.remaining() < Solver::default().remaining());
+            assert_eq!(copy.guess(&history), solver.guess(&history));
+        }
+
+        #[test]
+        fn reusing_matches_fresh() {
+            let w = Wordle::new();
+            let answers = ["cigar", "rebut", "sissy", "awake"];
+            let fresh = w.evaluate(Solver::default, &answers);
+            let reused = w.evaluate_reusing(&mut Solver::default(), &answers);
+            assert_eq!(super::untimed(fresh), super::untimed(reused));
+        }
+    }
+    mod absent {
+        use crate::{remaining_candidates, Correctness, Guess, Guesser, Solver, DICTIONARY};
+        use std::borrow::Cow;
+
+        fn bits(letters: &str) -> u32 {
+            letters.bytes().fold(0, |bits, l| bits | 1 << (l - b'a'))
+        }
+
+        #[test]
+        fn colored_elsewhere_is_not_absent() {
+            for (mask, absent) in [
+                (mask![C W W W W], "b"),
+                (mask![W W M W W], "a"),
+                (mask![W C W W C], ""),
+                (mask![W W W W W], "ab"),
+            ] {
+                let g = Guess {
+                    word: Cow::Borrowed("aabbb"),
+                    mask,
+                };
+                assert_eq!(g.absent_letters(), bits(absent), "{:?}", mask);
+            }
+
+            let g = Guess {
+                word: Cow::Borrowed("eerie"),
+                mask: Correctness::compute("there", "eerie"),
+            };
+            assert_eq!(g.absent_letters(), bits("i"));
+        }
+
+        #[test]
+        fn solver_prunes_like_matches() {
+            let words = || DICTIONARY.iter().map(|&(w, _)| w);
+            for answer in ["geese", "there", "mamma", "added"] {
+                let mut solver = Solver::default();
+                let mut history = Vec::new();
+                // Alternate between guesses of our own and the solver's, which it prunes with the
+                // correctness cache, so that both ways of pruning are covered.
+                for forced in ["eerie", "", "mamma", ""] {
+                    let word = if forced.is_empty() {
+                        solver.guess(&history)
+                    } else {
+                        forced.to_string()
+                    };
+                    history.push(Guess {
+                        mask: Correctness::compute(answer, &word),
+                        word: Cow::Owned(word),
+                    });
+                    if history.last().unwrap().word == answer {
+                        break;
+                    }
+                    solver.guess(&history);
+                    assert_eq!(
+                        solver.remaining(),
+                        Some(remaining_candidates(words(), &history).len()),
+                        "{} {:?}",
+                        answer,
+                        history
+                    );
+                }
+            }
+        }
+    }
+    mod observer {
+        use crate::{Correctness, Wordle};
+
+        #[test]
+        fn called_every_turn() {
+            let w = Wordle::new();
+            let mut words = vec!["right", "sight", "trace"];
+            let mut turns = Vec::new();
+            let score = w.play_with_observer(
+                "right",
+                |_: &[crate::Guess]| words.pop().unwrap().to_string(),
+                |turn, guess: &str, mask: &[Correctness; 5], left| {
+                    turns.push((turn, guess.to_string(), *mask, left));
+                },
+            );
+            assert_eq!(score, Some(3));
+            assert_eq!(turns.len(), 3);
+            for (i, (turn, guess, mask, _)) in turns.iter().enumerate() {
+                assert_eq!(*turn, i + 1);
+                assert_eq!(*mask, Correctness::compute("right", guess));
+            }
+            assert!(turns[0].3 > turns[1].3);
+            assert!(turns[1].3 >= 1);
+            assert_eq!(turns[2].3, 1);
+        }
+    }
+    mod openings {
+        use crate::{Correctness, Guess, Guesser, Solver, UnknownOpening, Wordle};
+        use std::{borrow::Cow, collections::HashMap, sync::Arc};
+
+        #[test]
+        fn same_as_live() {
+            let live = Solver::default();
+            let table = live.precompute_openings();
+            // Every mask but all green can follow "trace" for some answer.
+            assert!(table.len() > 100);
+            assert!(!table.contains_key(&[Correctness::Correct; 5]));
+
+            let loaded = Solver::builder()
+                .with_openings(table.clone())
+                .unwrap()
+                .build();
+            for (&mask, word) in &table {
+                let history = [Guess {
+                    word: Cow::Borrowed("trace"),
+                    mask,
+                }];
+                let mut with = loaded.fresh();
+                assert_eq!(with.guess(&[]), "trace");
+                assert_eq!(with.guess(&history), *word);
+                let mut without = live.fresh();
+                without.guess(&[]);
+                assert_eq!(without.guess(&history), *word, "{:?}", mask);
+            }
+
+            let w = Wordle::new();
+            for answer in ["cigar", "humph", "fight", "trace", "zesty"] {
+                assert_eq!(
+                    w.play_recording(answer, loaded.fresh()),
+                    w.play_recording(answer, live.fresh())
+                );
+            }
+        }
+
+        #[test]
+        fn only_after_the_opener() {
+            let mut table = Solver::default().precompute_openings();
+            let mask = Correctness::compute("humph", "trace");
+            table.insert(mask, "zesty".to_string());
+            let mut solver = Solver::builder().with_openings(table).unwrap().build();
+            let history = [Guess {
+                word: Cow::Borrowed("trace"),
+                mask,
+            }];
+            assert_eq!(solver.guess(&history), "zesty");
+
+            // Another first guess is worked out as usual.
+            let history = [Guess {
+                word: Cow::Borrowed("crane"),
+                mask: Correctness::compute("humph", "crane"),
+            }];
+            assert_eq!(
+                solver.fresh().guess(&history),
+                Solver::default().guess(&history)
+            );
+        }
+
+        #[test]
+        fn unknown_words() {
+            let mask = Correctness::compute("humph", "trace");
+            let mut table = HashMap::new();
+            table.insert(mask, "qxzzy".to_string());
+            assert_eq!(
+                Solver::builder().with_openings(table.clone()).err(),
+                Some(UnknownOpening {
+                    mask,
+                    word: "qxzzy".to_string()
+                })
+            );
+
+            // Set directly, the word is passed over rather than played.
+            let mut options = Solver::builder();
+            options.openings = Some(Arc::new(table));
+            let history = [Guess {
+                word: Cow::Borrowed("trace"),
+                mask,
+            }];
+            assert_eq!(
+                options.build().guess(&history),
+                Solver::default().guess(&history)
+            );
+        }
+    }
+    mod verbose {
+        use crate::{Solver, Wordle};
+
+        #[test]
+        fn solver_reports_counts() {
+            let w = Wordle::new();
+            let trace = w.play_verbose("hatch", Solver::default());
+            assert_eq!(Some(trace.turns.len()), trace.score);
+            assert_eq!(trace.turns.last().unwrap().guess, "hatch");
+
+            let counts: Vec<_> = trace
+                .turns
+                .iter()
+                .map(|t| t.remaining_after.unwrap())
+                .collect();
+            assert_eq!(counts.last(), Some(&1));
+            assert!(counts.windows(2).all(|w| w[0] >= w[1]), "{:?}", counts);
+            for (i, turn) in trace.turns.iter().enumerate() {
+                let history: Vec<_> = trace.turns[..=i]
+                    .iter()
+                    .map(|t| crate::Guess {
+                        word: std::borrow::Cow::Borrowed(&*t.guess),
+                        mask: t.mask,
+                    })
+                    .collect();
+                assert_eq!(turn.remaining_after, Some(w.candidates(&history).len()));
+            }
+        }
+
+        #[test]
+        fn silent_guessers() {
+            let w = Wordle::new();
+            let trace = w.play_verbose(
+                "right",
+                guesser!(|history| { ["wrong", "right"][history.len()].to_string() }),
+            );
+            assert_eq!(trace.score, Some(2));
+            assert_eq!(trace.turns.len(), 2);
+            assert!(trace.turns.iter().all(|t| t.remaining_after.is_none()));
+        }
+    }
+    mod recording {
+        use crate::{turn, Guess, GuessOutcome, Solver, Wordle};
+
+        #[test]
+        fn solved() {
+            let w = Wordle::new();
+            let outcome = w.play_recording(
+                "right",
+                guesser!(|history| { ["wrong", "right"][turn(history) - 1].to_string() }),
+            );
+            assert!(matches!(outcome, GuessOutcome::Solved(_)));
+            assert_eq!(outcome.score(), Some(2));
+            let words: Vec<_> = outcome.history().iter().map(|g| &*g.word).collect();
+            assert_eq!(words, ["wrong", "right"]);
+            assert_eq!(outcome.history()[0].mask, mask![W M W W M]);
+        }
+
+        #[test]
+        fn out_of_guesses() {
+            let w = Wordle::new();
+            let outcome = w.play_recording("right", guesser!(|_history| { "wrong".to_string() }));
+            assert_eq!(outcome.score(), None);
+            assert_eq!(outcome.into_history().len(), 32);
+        }
+
+        #[test]
+        fn matches_a_real_game() {
+            let w = Wordle::new();
+            for answer in ["crane", "vivid", "jazzy"] {
+                let outcome = w.play_recording(answer, Solver::default());
+                assert_eq!(outcome.score(), w.play(answer, Solver::default()));
+                assert_eq!(outcome.score(), Some(outcome.history().len()));
+
+                // Replaying the recorded guesses one by one gives back the same game.
+                let mut replay = outcome.history().iter();
+                let replayed = w.play_recording(answer, |history: &[Guess]| {
+                    assert_eq!(turn(history), history.len() + 1);
+                    replay
+                        .next()
+                        .expect("no more guesses than recorded")
+                        .word
+                        .to_string()
+                });
+                assert_eq!(replayed.history(), outcome.history());
+            }
+        }
+
+        #[test]
+        #[allow(deprecated)]
+        fn recorded_forwards() {
+            let w = Wordle::new();
+            let (score, history) = w.play_recorded("crane", Solver::default());
+            let outcome = w.play_recording("crane", Solver::default());
+            assert_eq!(score, outcome.score());
+            assert_eq!(history, outcome.into_history());
+        }
+    }
+    mod solves_within {
+        use crate::Wordle;
+
+        #[test]
+        fn reports_slow_answers_with_traces() {
+            let w = Wordle::new();
+            let make = || {
+                guesser!(|history| {
+                    ["wrong", "right", "sight", "night"][history.len() % 4].to_string()
+                })
+            };
+            assert_eq!(w.assert_solves_within(&["wrong", "right"], make, 2), Ok(()));
+
+            let failures = w
+                .assert_solves_within(&["wrong", "sight", "night"], make, 3)
+                .unwrap_err();
+            assert_eq!(failures.len(), 1);
+            let (answer, trace) = &failures[0];
+            assert_eq!(*answer, "night");
+            let words: Vec<_> = trace.iter().map(|g| &*g.word).collect();
+            assert_eq!(words, ["wrong", "right", "sight", "night"]);
+            assert_eq!(trace[0].mask, mask![W W W M M]);
+            assert_eq!(trace[3].mask, mask![C C C C C]);
+        }
+    }
+    mod coverage {
+        use crate::{Filter, Wordle};
+
+        #[test]
+        fn splits_answers_by_limit() {
+            let w =
+                Wordle::from_dictionary("hatch 5\nmatch 4\npatch 3\nlatch 2\nbatch 1\n").unwrap();
+            // Always guesses the first word in this order that is still possible, so `hatch`
+            // takes one guess, `match` two, and the rest have to get past `match` too.
+            let make = || {
+                guesser!(|history| {
+                    let mut f = Filter::new(["hatch", "match", "patch", "latch", "batch"]);
+                    for guess in history {
+                        f.observe(guess);
+                    }
+                    let first = f.iter().next().unwrap();
+                    first.to_string()
+                })
+            };
+
+            let c = w.coverage(make, 2);
+            assert_eq!(c.max, 2);
+            assert_eq!(c.solved, ["hatch", "match"]);
+            assert_eq!(c.failed, ["batch", "latch", "patch"]);
+            assert!(!c.is_complete());
+
+            assert!(w.coverage(make, 5).is_complete());
+            assert_eq!(w.coverage(make, 0).solved, Vec::<&str>::new());
+        }
+    }
+    mod hints {
+        use crate::{Guess, Guesser, Solver, Wordle};
+
+        #[derive(Default)]
+        struct Listener {
+            revealed: Vec<(usize, u8)>,
+        }
+
+        impl Guesser for &mut Listener {
+            fn guess(&mut self, _history: &[Guess]) -> String {
+                "wrong".to_string()
+            }
+
+            fn reveal(&mut self, position: usize, letter: u8) {
+                self.revealed.push((position, letter));
+            }
+        }
+
+        #[test]
+        fn reveals_unknown_letters_left_to_right() {
+            let w = Wordle::new();
+            let mut listener = Listener::default();
+            assert_eq!(w.play_with_hints("wrist", &mut listener, 2), None);
+            // `wrong` already gives away `w` and `r`.
+            assert_eq!(listener.revealed, [(2, b'i'), (3, b's'), (4, b't')]);
+        }
+
+        #[test]
+        fn hints_help_the_solver() {
+            let w = Wordle::new();
+            for answer in ["goner", "hatch"] {
+                let plain = w.play(answer, Solver::default()).unwrap();
+                let hinted = w.play_with_hints(answer, Solver::default(), 1).unwrap();
+                assert!(hinted <= plain, "{}", answer);
+            }
+        }
+    }
+    mod packed {
+        use crate::{Correctness, PackedCorrectness, MAX_MASK_ENUM};
+        use itertools::Itertools;
+
+        fn all_masks() -> impl Iterator<Item = [Correctness; 5]> {
+            let c = [
+                Correctness::Correct,
+                Correctness::Misplaced,
+                Correctness::Wrong,
+            ];
+            (0..5)
+                .map(|_| c)
+                .multi_cartesian_product()
+                .map(|m| [m[0], m[1], m[2], m[3], m[4]])
+        }
+
+        #[test]
+        fn patterns() {
+            let patterns: Vec<_> = Correctness::patterns().collect();
+            assert_eq!(patterns.len(), Correctness::PATTERN_COUNT);
+            // Every mask appears, exactly once.
+            assert!(patterns.iter().all_unique());
+            assert_eq!(all_masks().count(), Correctness::PATTERN_COUNT);
+            assert!(all_masks().all(|m| patterns.contains(&m)));
+
+            for (i, p) in patterns.iter().enumerate() {
+                let idx = Correctness::pattern_index(p);
+                assert_eq!(idx, i);
+                assert_eq!(Correctness::pattern_from_index(idx), *p);
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn pattern_index_out_of_range() {
+            Correctness::pattern_from_index(Correctness::PATTERN_COUNT);
+        }
+
+        #[test]
+        fn round_trip() {
+            let mut seen = [false; MAX_MASK_ENUM];
+            for mask in all_masks() {
+                let packed = PackedCorrectness::from_array(&mask);
+                assert_eq!(packed.to_array(), mask);
+                let idx = usize::from(u8::from(packed));
+                assert!(!seen[idx], "{:?} collides", mask);
+                seen[idx] = true;
+            }
+            assert!(seen.iter().all(|&s| s));
+        }
+
+        #[test]
+        fn stable_encoding() {
+            let byte = |m| u8::from(PackedCorrectness::from_array(&m));
+            assert_eq!(byte(mask![C C C C C]), 0);
+            assert_eq!(byte(mask![C C C C M]), 1);
+            assert_eq!(byte(mask![C C C M C]), 3);
+            assert_eq!(byte(mask![M C C C C]), 81);
+            assert_eq!(byte(mask![W W W W W]), 242);
+        }
+
+        #[test]
+        fn compute_packed() {
+            assert_eq!(
+                Correctness::compute_packed("aabbb", "caacc").to_array(),
+                mask![W C M W W]
+            );
+        }
+    }
+    mod dictionary {
+        use crate::{
+            check_word, diff_dictionaries, parse_dictionary, validate_dictionary, DictionaryError,
+            DictionaryIssue, WordError, Wordle,
+        };
+        use std::io::Cursor;
+
+        #[test]
+        fn counts_fit_a_narrow_usize() {
+            use crate::dictionary::{fit_counts, DICTIONARY_TEXT};
+
+            let text = fit_counts(DICTIONARY_TEXT, u32::MAX.into());
+            let counts: Vec<u64> = text
+                .lines()
+                .map(|l| l.split_once(' ').unwrap().1.parse().unwrap())
+                .collect();
+            assert_eq!(counts.len(), DICTIONARY_TEXT.lines().count());
+            assert!(counts.iter().all(|&c| (1..=u32::MAX.into()).contains(&c)));
+            let w = Wordle::from_dictionary(&text).unwrap();
+            assert!(w.frequency("which") > w.frequency("aahed"));
+        }
+
+        #[test]
+        fn languages() {
+            use crate::Lang;
+
+            assert_eq!(Wordle::new_lang(Lang::En).len(), Wordle::new().len());
+            assert!(matches!(
+                Lang::En.dictionary(),
+                std::borrow::Cow::Borrowed(_)
+            ));
+
+            #[cfg(feature = "lang-fr")]
+            {
+                let w = Wordle::new_lang(Lang::Fr);
+                assert!(w.contains("monde"));
+                // Accents are stripped, and ligatures spelled out.
+                assert!(w.contains("ecole"));
+                assert!(w.contains("coeur"));
+                assert!(!w.contains("crane"));
+            }
+
+            #[cfg(feature = "lang-de")]
+            {
+                let w = Wordle::new_lang(Lang::De);
+                assert!(w.contains("stadt"));
+                assert!(w.contains("zwolf"));
+                // `schön` and `schon` are one word once stripped.
+                let text = crate::dictionary::DICTIONARY_DE_TEXT;
+                let count = |word: &str| {
+                    text.lines()
+                        .find_map(|l| {
+                            l.strip_prefix(word)?
+                                .strip_prefix(' ')?
+                                .parse::<usize>()
+                                .ok()
+                        })
+                        .unwrap()
+                };
+                assert_eq!(w.frequency("schon"), Some(count("schön") + count("schon")));
+            }
+        }
+
+        #[test]
+        fn reports_every_issue() {
+            let text =
+                "crane 10\n\ncranes 3\nslate\nTrace 4\nslate x\ncrane 2\n\ncrane 1\nhumph 7\n";
+            assert_eq!(
+                validate_dictionary(text),
+                [
+                    DictionaryIssue::Malformed(DictionaryError::WrongLength {
+                        line: 3,
+                        expected: 5,
+                        got: 6
+                    }),
+                    DictionaryIssue::Malformed(DictionaryError::MissingFrequency { line: 4 }),
+                    DictionaryIssue::Malformed(DictionaryError::NotLowercase {
+                        line: 5,
+                        index: 0,
+                        found: 'T'
+                    }),
+                    DictionaryIssue::Malformed(DictionaryError::InvalidFrequency { line: 6 }),
+                    DictionaryIssue::Duplicate { line: 7, first: 1 },
+                    DictionaryIssue::Duplicate { line: 9, first: 1 },
+                ]
+            );
+            assert_eq!(validate_dictionary(crate::dictionary::DICTIONARY_TEXT), []);
+        }
+
+        #[test]
+        fn diff() {
+            let a = "crane 10\nslate 4\ntrace 7\nhumph 1\n";
+            let b = "trace 9\n\ncrane 10\nsissy 2\nawake 3\n";
+            let diff = diff_dictionaries(a, b).unwrap();
+            assert_eq!(diff.only_a, ["humph", "slate"]);
+            assert_eq!(diff.only_b, ["awake", "sissy"]);
+            assert_eq!(diff.common, [("crane", 10, 10), ("trace", 7, 9)]);
+            assert_eq!(diff.frequency_changes().collect::<Vec<_>>(), [("trace", 2)]);
+            assert!(!diff.a_contains_b());
+
+            assert!(diff_dictionaries(a, "trace 1\n").unwrap().a_contains_b());
+            assert_eq!(
+                diff_dictionaries(a, "trace\n"),
+                Err(DictionaryError::MissingFrequency { line: 1 })
+            );
+        }
+
+        #[test]
+        fn bundled() {
+            let w = Wordle::try_new().unwrap();
+            for &(word, count) in &crate::DICTIONARY {
+                assert_eq!(w.frequency(word), Some(count), "{}", word);
+            }
+        }
+
+        #[test]
+        fn size_and_lookup() {
+            let w = Wordle::new();
+            let lines = crate::dictionary::DICTIONARY_TEXT
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .count();
+            assert_eq!(w.len(), lines);
+            assert!(!w.is_empty());
+            assert!(w.contains("trace"));
+            assert!(!w.contains("qxzzy"));
+            assert!(!w.contains("traces"));
+
+            let w = Wordle::with_lists("right 10\n", "wrong 3\n").unwrap();
+            assert_eq!(w.len(), 2);
+            assert!(w.contains("wrong"));
+        }
+
+        #[test]
+        fn custom() {
+            let w = Wordle::from_dictionary("right 10\n\nwrong 3\n").unwrap();
+            assert_eq!(
+                w.play("right", guesser!(|_history| { "right".to_string() })),
+                Some(1)
+            );
+        }
+
+        #[test]
+        fn frequencies() {
+            let w = Wordle::from_dictionary("wrong 3\nright 10\nsight 3\n").unwrap();
+            assert_eq!(w.frequency("right"), Some(10));
+            assert_eq!(w.frequency("night"), None);
+            assert_eq!(
+                w.words_by_frequency().collect::<Vec<_>>(),
+                [("right", 10), ("sight", 3), ("wrong", 3)]
+            );
+
+            let w = Wordle::new();
+            let (first, count) = w.words_by_frequency().next().unwrap();
+            assert_eq!((first, count), crate::DICTIONARY[0]);
+            assert_eq!(w.frequency(first), Some(count));
+        }
+
+        #[test]
+        fn missing_frequency() {
+            assert_eq!(
+                Wordle::from_dictionary("right 10\nwrong\n").err(),
+                Some(DictionaryError::MissingFrequency { line: 2 })
+            );
+        }
+
+        #[test]
+        fn wrong_length() {
+            let err = Wordle::from_dictionary("right 10\n\nwrongs 3")
+                .err()
+                .unwrap();
+            assert_eq!(
+                err,
+                DictionaryError::WrongLength {
+                    line: 3,
+                    expected: 5,
+                    got: 6
+                }
+            );
+            assert_eq!(err.line(), 3);
+        }
+
+        #[test]
+        fn literals() {
+            assert_eq!(word!("trace"), "trace");
+            assert!(crate::is_word(word!("trace")));
+            assert_eq!(
+                check_word("abc", 5),
+                Err(WordError::WrongLength {
+                    expected: 5,
+                    got: 3
+                })
+            );
+            assert_eq!(
+                check_word("abcdef", 5),
+                Err(WordError::WrongLength {
+                    expected: 5,
+                    got: 6
+                })
+            );
+        }
+
+        #[test]
+        fn not_lowercase_ascii() {
+            // Six bytes, but five characters, and the fourth isn't ascii.
+            assert_eq!(
+                check_word("café0", 5),
+                Err(WordError::NotLowercase {
+                    index: 3,
+                    found: 'é'
+                })
+            );
+            assert_eq!(
+                check_word("ABCDE", 5),
+                Err(WordError::NotLowercase {
+                    index: 0,
+                    found: 'A'
+                })
+            );
+            assert_eq!(check_word("abcde", 5), Ok("abcde"));
+            for word in ["café0", "ABCDE", "abc", "abcdef"] {
+                assert!(!crate::is_word(word), "{}", word);
+            }
+
+            assert_eq!(
+                Wordle::from_dictionary("right 10\ncafés 3\n").err(),
+                Some(DictionaryError::NotLowercase {
+                    line: 2,
+                    index: 3,
+                    found: 'é'
+                })
+            );
+            assert_eq!(
+                parse_dictionary::<5>("Right 10\n").err(),
+                Some(DictionaryError::NotLowercase {
+                    line: 1,
+                    index: 0,
+                    found: 'R'
+                })
+            );
+        }
+
+        #[test]
+        fn other_lengths() {
+            let words = parse_dictionary::<4>("abba 3\nbaba 1\n").unwrap();
+            assert_eq!(words, [(*b"abba", 3), (*b"baba", 1)]);
+            assert_eq!(
+                parse_dictionary::<6>("kebabs 3\nright 1\n"),
+                Err(DictionaryError::WrongLength {
+                    line: 2,
+                    expected: 6,
+                    got: 5
+                })
+            );
+        }
+
+        #[test]
+        fn reader() {
+            let w = Wordle::from_reader(Cursor::new("right 10\nwrong 3\n\nsight 2\n")).unwrap();
+            assert_eq!(w.candidates(&[]), ["right", "sight", "wrong"]);
+
+            assert_eq!(
+                Wordle::from_reader(Cursor::new("right 10\nwrong three\n")).err(),
+                Some(DictionaryError::InvalidFrequency { line: 2 })
+            );
+            assert_eq!(
+                Wordle::from_reader(Cursor::new(b"right 10\n\xffrong 3\n")).err(),
+                Some(DictionaryError::Unreadable { line: 2 })
+            );
+        }
+
+        #[test]
+        fn solver() {
+            use crate::Solver;
+
+            let text = "right 50\nsight 40\nnight 30\nfight 20\nwrong 10\nmight 5\n";
+            let w = Wordle::from_reader(Cursor::new(text)).unwrap();
+            let options = Solver::builder().with_dictionary(text).unwrap();
+            for answer in ["right", "sight", "night", "fight", "wrong", "might"] {
+                // Wordle itself checks every guess is in its dictionary.
+                let score = w.play(answer, options.clone().build());
+                assert!(score.is_some_and(|s| s <= 4), "{}: {:?}", answer, score);
+            }
+
+            assert_eq!(
+                Solver::builder().with_dictionary("right 1\nwrongs 1").err(),
+                Some(DictionaryError::WrongLength {
+                    line: 2,
+                    expected: 5,
+                    got: 6
+                })
+            );
+        }
+
+        #[test]
+        fn path() {
+            let path = std::env::temp_dir().join(format!("popoki-{}.txt", std::process::id()));
+            std::fs::write(&path, "right 10\nwrong 3\n").unwrap();
+            let w = Wordle::from_path(&path);
+            std::fs::write(&path, "right 10\nwrongs 3\n").unwrap();
+            let bad = Wordle::from_path(&path);
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(w.unwrap().candidates(&[]), ["right", "wrong"]);
+            assert_eq!(bad.err().unwrap().kind(), std::io::ErrorKind::InvalidData);
+            assert!(Wordle::from_path(&path).is_err());
+        }
+
+        #[test]
+        fn invalid_frequency() {
+            assert_eq!(
+                Wordle::from_dictionary("right ten").err(),
+                Some(DictionaryError::InvalidFrequency { line: 1 })
+            );
+            assert_eq!(
+                Wordle::from_dictionary("right -1").err(),
+                Some(DictionaryError::InvalidFrequency { line: 1 })
+            );
+        }
+    }
+    mod compute {
+        use crate::Correctness;
+        use proptest::prelude::*;
+
+        #[test]
+        fn all_green() {
+            assert_eq!(Correctness::compute("abcde", "abcde"), mask![C C C C C]);
+        }
+
+        #[test]
+        fn masks_sort() {
+            use std::collections::BTreeSet;
+
+            let masks: BTreeSet<[Correctness; 5]> = ["cigar", "rebut", "sissy", "awake", "crane"]
+                .iter()
+                .map(|answer| Correctness::compute(answer, "crane"))
+                .collect();
+            let masks: Vec<_> = masks.into_iter().collect();
+            assert_eq!(
+                masks,
+                [
+                    mask![C C C C C],
+                    mask![C M M W W],
+                    mask![W M W W M],
+                    mask![W W C W C],
+                    mask![W W W W W],
+                ]
+            );
+        }
+
+        #[test]
+        fn modes() {
+            use crate::ComputeMode;
+            // Two `a`s in the answer, three in the guess, one of them green.
+            assert_eq!(
+                Correctness::compute_with("aabbb", "caaac", ComputeMode::Nyt),
+                mask![W C M W W]
+            );
+            assert_eq!(
+                Correctness::compute_with("aabbb", "caaac", ComputeMode::AllOccurrences),
+                mask![W C M M W]
+            );
+            // With no more copies in the guess than in the answer, they agree.
+            for (answer, guess) in [("aabbb", "ccaac"), ("ccaac", "aabbb"), ("crane", "trace")] {
+                assert_eq!(
+                    Correctness::compute_with(answer, guess, ComputeMode::Nyt),
+                    Correctness::compute_with(answer, guess, ComputeMode::AllOccurrences)
+                );
+                assert_eq!(
+                    Correctness::compute(answer, guess),
+                    Correctness::compute_with(answer, guess, ComputeMode::Nyt)
+                );
+            }
+        }
+
+        #[test]
+        fn all_at_once() {
+            let guesses = ["trace", "eerie", "sissy", "right", "fight"];
+            let masks = Correctness::compute_all("fight", &guesses);
+            assert_eq!(masks.len(), guesses.len());
+            for (guess, mask) in guesses.iter().zip(&masks) {
+                assert_eq!(*mask, Correctness::compute("fight", guess), "{}", guess);
+            }
+            assert_eq!(masks[4], mask![C C C C C]);
+            assert!(Correctness::compute_all("fight", &[]).is_empty());
+        }
+
+        #[test]
+        fn all_gray() {
+            assert_eq!(Correctness::compute("abcde", "fghij"), mask![W W W W W]);
+        }
+
+        #[test]
+        fn all_yellow() {
+            assert_eq!(Correctness::compute("abcde", "eabcd"), mask![M M M M M]);
+        }
+
+        #[test]
+        fn repeat_green() {
+            assert_eq!(Correctness::compute("aabbb", "aaccc"), mask![C C W W W]);
+        }
+
+        #[test]
+        fn repeat_yellow() {
+            assert_eq!(Correctness::compute("aabbb", "ccaac"), mask![W W M M W]);
+        }
+
+        #[test]
+        fn repeat_some_green() {
+            assert_eq!(Correctness::compute("aabbb", "caacc"), mask![W C M W W]);
+        }
+
+        #[test]
+        fn only_one_yellow() {
+            assert_eq!(Correctness::compute("azzaz", "aaabb"), mask![C M W W W]);
+        }
+
+        #[test]
+        fn only_one_green() {
+            assert_eq!(Correctness::compute("baccc", "aaddd"), mask![W C W W W]);
+        }
+
+        #[test]
+        fn only_one_gray() {
+            assert_eq!(Correctness::compute("abcde", "aacde"), mask![C W C C C]);
+        }
+
+        #[test]
+        fn other_lengths() {
+            assert_eq!(Correctness::compute_n(b"abba", b"baba"), mask![M M C C]);
+            assert_eq!(
+                Correctness::compute_n(b"kebabs", b"abbeys"),
+                mask![M M C M W C]
+            );
+            assert_eq!(
+                Correctness::compute_n(b"aabbb", b"caacc"),
+                Correctness::compute("aabbb", "caacc")
+            );
+        }
+
+        #[test]
+        fn fast_agrees_on_examples() {
+            for (answer, guess) in [
+                ("abcde", "abcde"),
+                ("abcde", "fghij"),
+                ("abcde", "eabcd"),
+                ("aabbb", "aaccc"),
+                ("aabbb", "ccaac"),
+                ("aabbb", "caacc"),
+                ("azzaz", "aaabb"),
+                ("baccc", "aaddd"),
+                ("abcde", "aacde"),
+            ] {
+                assert_eq!(
+                    Correctness::compute_fast(answer, guess),
+                    Correctness::compute(answer, guess),
+                    "{} guessed for {}",
+                    guess,
+                    answer
+                );
+            }
+        }
+
+        // Small alphabets make repeated letters common.
+        fn word() -> impl Strategy<Value = String> {
+            prop_oneof!["[a-b]{5}", "[a-c]{5}", "[a-e]{5}", "[a-z]{5}"]
+        }
+
+        proptest! {
+            #[test]
+            fn fast_agrees_on_random_words(answer in word(), guess in word()) {
+                prop_assert_eq!(
+                    Correctness::compute_fast(&answer, &guess),
+                    Correctness::compute(&answer, &guess),
+                    "{} guessed for {}",
+                    guess,
+                    answer
+                );
+            }
+        }
+    }
+    mod letter_info {
+        use crate::Correctness;
+
+        #[test]
+        fn two_guessed_one_in_answer() {
+            let (mask, info) = Correctness::analyze("steak", "sleet");
+            assert_eq!(mask, mask![C W C W M]);
+            assert_eq!(info.min_count(b'e'), 1);
+            assert_eq!(info.exact_count(b'e'), Some(1));
+        }
+
+        #[test]
+        fn two_yellows_is_a_lower_bound() {
+            let (mask, info) = Correctness::analyze("ember", "there");
+            assert_eq!(mask, mask![W W M M M]);
+            assert_eq!(info.min_count(b'e'), 2);
+            assert_eq!(info.exact_count(b'e'), None);
+            assert_eq!(info.min_count(b'r'), 1);
+        }
+
+        #[test]
+        fn gray_only_is_exactly_zero() {
+            let (_, info) = Correctness::analyze("ember", "thigh");
+            assert_eq!(info.min_count(b't'), 0);
+            assert_eq!(info.exact_count(b't'), Some(0));
+            // `z` wasn't guessed, so nothing is known about it.
+            assert_eq!(info.exact_count(b'z'), None);
+        }
+
+        #[test]
+        fn other_bytes_are_unknown() {
+            let (_, info) = Correctness::analyze("ember", "there");
+            for letter in [b'E', b'!', b'{', 0xff] {
+                assert_eq!(info.min_count(letter), 0);
+                assert_eq!(info.exact_count(letter), None);
+            }
+        }
+    }
+    mod candidate_stores {
+        use crate::{
+            algorithms::{Minimax, Random},
+            Correctness, Filter, Guess, Guesser, Solver, Wordle,
+        };
+        use std::borrow::Cow;
+
+        const TEXT: &str = "right 50\nsight 40\nnight 30\nfight 20\nwrong 10\nmight 5\n\
+                            light 4\ntight 3\nhumph 2\ncrane 1\n";
+        /// [`TEXT`], parsed.
+        const WORDS: &[(&str, usize)] = &[
+            ("right", 50),
+            ("sight", 40),
+            ("night", 30),
+            ("fight", 20),
+            ("wrong", 10),
+            ("might", 5),
+            ("light", 4),
+            ("tight", 3),
+            ("humph", 2),
+            ("crane", 1),
+        ];
+
+        #[test]
+        fn agree_on_what_is_left() {
+            let options = Solver::builder().with_dictionary(TEXT).unwrap();
+            let dictionary = WORDS;
+            let w = Wordle::from_dictionary(TEXT).unwrap();
+            for answer in ["night", "humph", "tight"] {
+                let mut solver = options.clone().build();
+                let mut minimax = Minimax::with_dictionary(dictionary);
+                let mut random = Random::with_dictionary(dictionary, 3);
+                let mut filter = Filter::new(dictionary.iter().map(|&(word, _)| word));
+                let mut history = Vec::new();
+                for word in ["crane", "sight", "wrong", answer] {
+                    history.push(Guess {
+                        word: Cow::Borrowed(word),
+                        mask: Correctness::compute(answer, word),
+                    });
+                    filter.observe(history.last().unwrap());
+                    let expected = w.candidates(&history);
+                    let mut left: Vec<_> = filter.iter().collect();
+                    left.sort_unstable();
+                    assert_eq!(left, expected);
+                    assert_eq!(w.estimate_remaining(&history), expected.len());
+
+                    let guessers: [&mut dyn Guesser; 3] = [&mut solver, &mut minimax, &mut random];
+                    for guesser in guessers {
+                        guesser.guess(&history);
+                        assert_eq!(guesser.remaining(), Some(expected.len()), "{:?}", history);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "compute-cache")]
+        fn compute_cache_gives_the_same_games() {
+            // Custom dictionaries get a table of their own, since the thread-local cache only
+            // covers the bundled one.
+            let cached = Solver::builder().with_dictionary(TEXT).unwrap();
+            assert_eq!(cached.compute_cache.as_ref().map(|c| c.len()), Some(10));
+            let mut options = Solver::builder();
+            options.cache = false;
+            let options = options.with_dictionary(TEXT).unwrap();
+            assert!(options.compute_cache.is_none());
+
+            let w = Wordle::from_dictionary(TEXT).unwrap();
+            for answer in ["night", "humph", "tight", "crane"] {
+                assert_eq!(
+                    w.play_recording(answer, cached.clone().build()),
+                    w.play_recording(answer, options.clone().build())
+                );
+            }
+        }
+
+        #[test]
+        fn storage_does_not_change_the_guess() {
+            // The cache and the parallel scan only change how masks are stored and computed.
+            let configs = || {
+                let mut configs = Vec::new();
+                for cache in [true, false] {
+                    let mut options = Solver::builder();
+                    options.cache = cache;
+                    configs.push(options.clone());
+                    #[cfg(feature = "parallel")]
+                    {
+                        options.parallel = !options.parallel;
+                        configs.push(options);
+                    }
+                }
+                configs
+            };
+            for answer in ["cigar", "humph", "watch"] {
+                let games: Vec<_> = configs()
+                    .into_iter()
+                    .map(|options| Wordle::new().play_recording(answer, options.build()))
+                    .collect();
+                assert!(games.windows(2).all(|g| g[0] == g[1]), "{:?}", games);
+            }
+        }
+    }
+    mod invariants {
+        use crate::{algorithms::Random, Correctness, Guess, Wordle, DICTIONARY};
+        use proptest::prelude::*;
+        use std::{
+            panic::{catch_unwind, AssertUnwindSafe},
+            sync::OnceLock,
+        };
+
+        fn wordle() -> &'static Wordle {
+            static WORDLE: OnceLock<Wordle> = OnceLock::new();
+            WORDLE.get_or_init(Wordle::new)
+        }
+
+        fn answer() -> impl Strategy<Value = &'static str> {
+            (0..DICTIONARY.len()).prop_map(|i| DICTIONARY[i].0)
+        }
+
+        /// Every mask in a finished game is the one the answer gives, and only the last one,
+        /// if any, is all green.
+        fn check_history(answer: &str, history: &[Guess]) -> Result<(), TestCaseError> {
+            for (i, guess) in history.iter().enumerate() {
+                prop_assert_eq!(guess.mask, Correctness::compute(answer, &guess.word));
+                let won = guess.mask == [Correctness::Correct; 5];
+                prop_assert_eq!(won, guess.word == answer);
+                prop_assert!(!won || i + 1 == history.len());
+            }
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn answer_against_itself_is_all_green(answer in answer()) {
+                prop_assert_eq!(Correctness::compute(answer, answer), [Correctness::Correct; 5]);
+            }
+
+            /// A scripted guesser plays dictionary words, then the answer.
+            #[test]
+            fn score_is_the_turn_the_answer_was_guessed(
+                answer in answer(),
+                script in prop::collection::vec(answer(), 0..8),
+            ) {
+                let turn = script.iter().position(|&w| w == answer).unwrap_or(script.len()) + 1;
+                let mut words = script.into_iter().chain([answer]);
+                let outcome = wordle()
+                    .play_recording(answer, |_: &[Guess]| words.next().unwrap().to_string());
+                prop_assert_eq!(outcome.score(), Some(turn));
+                prop_assert_eq!(outcome.history().len(), turn);
+                check_history(answer, outcome.history())?;
+            }
+
+            #[test]
+            fn random_guesser_wins(answer in answer(), seed in any::<u64>()) {
+                let outcome = wordle().play_recording(answer, Random::new(seed));
+                prop_assert!(outcome.score().is_some());
+                check_history(answer, outcome.history())?;
+            }
+
+            #[test]
+            fn unknown_guesses_panic(answer in answer(), guess in "[a-z]{4,6}") {
+                prop_assume!(!wordle().contains(&guess) && guess != answer);
+                let played = catch_unwind(AssertUnwindSafe(|| {
+                    wordle().play(answer, |_: &[Guess]| guess.clone())
+                }));
+                prop_assert!(played.is_err());
+            }
+        }
+    }
+    mod symmetry {
+        use crate::{Correctness, Guess};
+        use proptest::prelude::*;
+        use std::borrow::Cow;
+
+        // Swapping the answer and the guess does *not* give the same mask in general. Yellows
+        // are reported where the letter sits in the guess, so even words without repeated
+        // letters disagree, and with repeats it can be a different copy of a letter that is
+        // colored.
+        #[test]
+        fn counterexamples() {
+            assert_eq!(Correctness::compute("crane", "trace"), mask![W C C M C]);
+            assert_eq!(Correctness::compute("trace", "crane"), mask![M C C W C]);
+
+            assert_eq!(Correctness::compute("speed", "crepe"), mask![W W C M M]);
+            assert_eq!(Correctness::compute("crepe", "speed"), mask![W M C M W]);
+
+            assert_eq!(Correctness::compute("those", "shoot"), mask![M C C W M]);
+            assert_eq!(Correctness::compute("shoot", "those"), mask![M C C M W]);
+        }
+
+        fn non_gray(word: &str, mask: &[Correctness; 5], letter: u8) -> usize {
+            word.bytes()
+                .zip(mask)
+                .filter(|&(l, &c)| l == letter && c != Correctness::Wrong)
+                .count()
+        }
+
+        // Smaller alphabets, so that repeated letters are common.
+        fn word() -> impl Strategy<Value = String> {
+            prop_oneof!["[a-c]{5}", "[a-f]{5}", "[a-z]{5}"]
+        }
+
+        proptest! {
+            /// What does survive the swap: the greens, and how many of each letter are colored.
+            #[test]
+            fn greens_and_letter_counts_are_symmetric(answer in word(), guess in word()) {
+                let forward = Correctness::compute(&answer, &guess);
+                let backward = Correctness::compute(&guess, &answer);
+                for (f, b) in forward.iter().zip(&backward) {
+                    prop_assert_eq!(*f == Correctness::Correct, *b == Correctness::Correct);
+                }
+                for letter in b'a'..=b'z' {
+                    prop_assert_eq!(
+                        non_gray(&guess, &forward, letter),
+                        non_gray(&answer, &backward, letter)
+                    );
+                }
+            }
+
+            /// `Guess::matches` only ever computes the mask with the candidate as the answer.
+            #[test]
+            fn matches_agrees_with_compute(answer in word(), guess in word(), word in word()) {
+                let g = Guess {
+                    word: Cow::Borrowed(&guess),
+                    mask: Correctness::compute(&answer, &guess),
+                };
+                prop_assert!(g.matches(&answer));
+                prop_assert_eq!(g.matches(&word), Correctness::compute(&word, &guess) == g.mask);
+                prop_assert_eq!(g.allows(&word), g.matches(&word));
+                prop_assert_eq!(g.prepare().allows(&word), g.matches(&word));
+            }
         }
     }
 }
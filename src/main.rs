@@ -11,14 +11,26 @@
     clippy::print_stdout,
     clippy::option_if_let_else,
     clippy::expect_used,
-    clippy::integer_arithmetic,
-    clippy::separated_literal_suffix,
+    clippy::arithmetic_side_effects,
     clippy::cast_possible_truncation,
-    clippy::cast_sign_loss
+    clippy::cast_sign_loss,
+    clippy::struct_excessive_bools,
+    clippy::missing_inline_in_public_items,
+    clippy::std_instead_of_alloc
+)]
+// Restriction lints added to clippy after the list above was written.
+#![allow(
+    clippy::allow_attributes_without_reason,
+    clippy::arbitrary_source_item_ordering,
+    clippy::doc_paragraphs_missing_punctuation,
+    clippy::min_ident_chars,
+    clippy::absolute_paths,
+    clippy::single_call_fn
 )]
 
-use clap::{Parser, ValueEnum};
-use popoki::{Guesser, Solver};
+use clap::{Parser, Subcommand, ValueEnum};
+use popoki::{Correctness, Guess, Guesser, Solver};
+use std::{borrow::Cow, io::Write as _};
 
 const GAMES: &str = include_str!("../answers.txt");
 
@@ -46,11 +58,37 @@ struct Args {
     #[clap(long)]
     easy: bool,
 
+    /// Lets hard mode play for information rather than the answer once few candidates remain,
+    /// as long as this many spare turns are left after the probe and the answer.
+    #[clap(long)]
+    explore: Option<usize>,
+
+    /// Play the games once with every way of ranking candidates, and print a table comparing
+    /// them instead of a histogram.
+    #[clap(long)]
+    compare: bool,
+
+    /// Print the comparison table as markdown.
+    #[clap(long, requires = "compare")]
+    markdown: bool,
+
     /// The number of games to run.
     ///
     /// If not passed, all Wordle games are run.
     #[clap(short, long, default_value_t = 5)]
     games: usize,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Suggest guesses for a game played elsewhere, reading back the colors each one got.
+    ///
+    /// Colors are typed as five letters: G (or C) for green, Y (or M) for yellow, and W for
+    /// gray.
+    Solve,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy)]
@@ -84,15 +122,121 @@ fn main() {
     if args.easy {
         solver.hard_mode = false;
     }
-    solver.rank_by = match args.rank_by {
-        Rank::First => popoki::Rank::First,
-        Rank::ExpectedScore => popoki::Rank::ExpectedScore,
-        Rank::WeightedInformation => popoki::Rank::WeightedInformation,
-        Rank::InfoPlusProbability => popoki::Rank::InfoPlusProbability,
-        Rank::ExpectedInformation => popoki::Rank::ExpectedInformation,
-    };
-
-    play(move || solver.build(), args.games);
+    if let Some(slack) = args.explore {
+        solver = solver.with_exploration(slack);
+    }
+    solver.rank_by = args.rank_by.into();
+
+    if matches!(args.command, Some(Command::Solve)) {
+        solve(solver);
+    } else if args.compare {
+        compare(&solver, args.games, args.markdown);
+    } else {
+        play(move || solver.clone().build(), args.games);
+    }
+}
+
+impl From<Rank> for popoki::Rank {
+    fn from(rank: Rank) -> Self {
+        match rank {
+            Rank::First => Self::First,
+            Rank::ExpectedScore => Self::ExpectedScore,
+            Rank::WeightedInformation => Self::WeightedInformation,
+            Rank::InfoPlusProbability => Self::InfoPlusProbability,
+            Rank::ExpectedInformation => Self::ExpectedInformation,
+        }
+    }
+}
+
+fn compare(options: &popoki::Options, max: usize, markdown: bool) {
+    let w = popoki::Wordle::new();
+    let answers: Vec<&'static str> = GAMES.split_whitespace().take(max).collect();
+    let mut board = popoki::Leaderboard::new();
+
+    for &rank in Rank::value_variants() {
+        let mut solver = options.clone();
+        solver.rank_by = rank.into();
+        let name = rank
+            .to_possible_value()
+            .expect("no ranks are skipped")
+            .get_name()
+            .to_owned();
+        board.compete(name, &w, &answers, || solver.clone().build());
+    }
+
+    if markdown {
+        print!("{}", board.to_markdown());
+    } else {
+        print!("{}", board.to_table());
+    }
+}
+
+fn solve(options: popoki::Options) {
+    let mut solver = options.build();
+    let mut history: Vec<Guess<'static>> = Vec::new();
+
+    loop {
+        println!("{} candidates left", candidates(&history));
+        let guess = solver.guess(&history);
+        println!("guess: {guess}");
+
+        let Some(mask) = read_mask(&guess, &history) else {
+            return;
+        };
+        if mask == [Correctness::Correct; 5] {
+            println!("solved in {}", history.len() + 1);
+            return;
+        }
+        history.push(Guess {
+            word: Cow::Owned(guess),
+            mask,
+        });
+    }
+}
+
+/// How many words could still be the answer.
+fn candidates(history: &[Guess]) -> usize {
+    popoki::remaining_candidates(popoki::DICTIONARY.iter().map(|&(word, _)| word), history).len()
+}
+
+/// Prompt for the colors `guess` got until they parse and leave some candidates. Returns `None`
+/// once stdin is closed.
+fn read_mask(guess: &str, history: &[Guess]) -> Option<[Correctness; 5]> {
+    let mut line = String::new();
+    loop {
+        print!("colors: ");
+        std::io::stdout().flush().expect("stdout is writable");
+        line.clear();
+        if std::io::stdin()
+            .read_line(&mut line)
+            .expect("stdin is readable")
+            == 0
+        {
+            return None;
+        }
+
+        let mask = match popoki::parse_share_grid(&line).as_deref() {
+            Ok(&[mask]) => mask,
+            Ok(_) => {
+                eprintln!("enter five colors, like WYWWG");
+                continue;
+            }
+            Err(e) => {
+                eprintln!("{e}; enter five colors, like WYWWG");
+                continue;
+            }
+        };
+        let mut with = history.to_vec();
+        with.push(Guess {
+            word: Cow::Borrowed(guess),
+            mask,
+        });
+        if mask != [Correctness::Correct; 5] && candidates(&with) == 0 {
+            eprintln!("no word fits those colors, check them and try again");
+            continue;
+        }
+        return Some(mask);
+    }
 }
 
 fn play<G>(mut mk: impl FnMut() -> G, max: usize)
@@ -110,7 +254,7 @@ where
             games += 1_i32;
             score += s;
             if s >= histogram.len() {
-                histogram.extend(std::iter::repeat(0).take(s - histogram.len() + 1));
+                histogram.extend(core::iter::repeat_n(0, s - histogram.len() + 1));
             }
             *histogram
                 .get_mut(s)
@@ -152,4 +296,32 @@ mod tests {
             [3, 4, 4, 4, 4, 5, 4, 4, 3, 3, 4, 3, 4, 3, 5, 2, 3, 3, 3, 4]
         );
     }
+
+    #[test]
+    fn exploration() {
+        let w = popoki::Wordle::new();
+        // Answers the greedy solver needs six guesses for.
+        let answers = || {
+            crate::GAMES
+                .split_whitespace()
+                .take(20)
+                .chain(["picky", "pleat", "wacky"])
+        };
+        let greedy: Vec<usize> = answers()
+            .map(|answer| w.play(answer, popoki::Solver::default()).expect("solves"))
+            .collect();
+        // Exploring stays within hard mode's rules.
+        let explore: Vec<usize> = answers()
+            .map(|answer| {
+                let solver = popoki::Solver::builder().with_exploration(0).build();
+                w.play_hard_mode(answer, solver)
+                    .expect("fits every clue")
+                    .expect("solves")
+            })
+            .collect();
+
+        let mean = |s: &[usize]| s.iter().sum::<usize>() as f64 / s.len() as f64;
+        assert!(explore.iter().max() < greedy.iter().max());
+        assert!(mean(&explore) < mean(&greedy) + 0.1_f64);
+    }
 }
@@ -0,0 +1,571 @@
+//! The pure part of the crate: masks and the rules for computing and matching them.
+//!
+//! Nothing in here needs `std`, only `alloc` for the owned word in a [`Guess`], so it is all
+//! that is left when the `std` feature is turned off.
+
+use alloc::{borrow::Cow, vec::Vec};
+use core::{fmt, num::NonZeroU8};
+
+/// Why a string is not a word, see [`check_word`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordError {
+    /// The word does not have as many letters as expected.
+    WrongLength { expected: usize, got: usize },
+    /// The character at `index` (counting characters from 0) is not a lowercase ascii letter.
+    NotLowercase { index: usize, found: char },
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, got } => {
+                write!(f, "word has {} letters, expected {}", got, expected)
+            }
+            Self::NotLowercase { index, found } => {
+                write!(
+                    f,
+                    "'{}' at position {} is not a lowercase letter",
+                    found, index
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WordError {}
+
+/// Check that `word` is `len` letters from `a` to `z`, which is all that masks are computed
+/// for. Words are compared byte by byte, so accented letters, which take more than one byte,
+/// and uppercase letters are rejected rather than quietly giving nonsense.
+///
+/// Letters are checked before the length, so `café0` is reported as having a bad letter
+/// rather than six bytes.
+pub fn check_word(word: &str, len: usize) -> Result<&str, WordError> {
+    if let Some((index, found)) = word
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_lowercase())
+    {
+        return Err(WordError::NotLowercase { index, found });
+    }
+    if word.len() != len {
+        return Err(WordError::WrongLength {
+            expected: len,
+            got: word.len(),
+        });
+    }
+    Ok(word)
+}
+
+/// Whether [`check_word`] would accept `word` as a five-letter word, usable in constants. See
+/// [`word!`](crate::word!).
+pub const fn is_word(word: &str) -> bool {
+    let bytes = word.as_bytes();
+    if bytes.len() != 5 {
+        return false;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_lowercase() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// How one letter of a guess compares to the answer.
+///
+/// Colors are ordered from best to worst, `Correct < Misplaced < Wrong`, and masks compare
+/// letter by letter from the left, so an all-green mask sorts first and an all-gray one last.
+/// Masks are `Ord` and `Hash`, and work as keys of a `BTreeMap` or a `HashMap` as they are.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Correctness {
+    /// Green
+    #[cfg_attr(feature = "serde", serde(rename = "C"))]
+    Correct,
+    /// Yellow
+    #[cfg_attr(feature = "serde", serde(rename = "M"))]
+    Misplaced,
+    /// Gray
+    #[cfg_attr(feature = "serde", serde(rename = "W"))]
+    Wrong,
+}
+
+impl Correctness {
+    fn is_misplaced(letter: u8, answer: &str, used: &mut [bool; 5]) -> bool {
+        answer.bytes().enumerate().any(|(i, a)| {
+            if a == letter && !used[i] {
+                used[i] = true;
+                return true;
+            }
+            false
+        })
+    }
+
+    /// Given an answer and a guess, return an array of 5 elements, each of which is
+    /// a `Result` indicating whether the guess is correct, incorrect, or not present
+    ///
+    /// Both must be lowercase ascii, see [`check_word`]. Repeated letters are colored as in the
+    /// NYT game, see [`ComputeMode::Nyt`].
+    pub fn compute(answer: &str, guess: &str) -> [Self; 5] {
+        Self::compute_with(answer, guess, ComputeMode::Nyt)
+    }
+
+    /// Like [`Correctness::compute`], coloring repeated letters as `mode` says.
+    pub fn compute_with(answer: &str, guess: &str, mode: ComputeMode) -> [Self; 5] {
+        // Sanity check for `answer` and `guess`
+        assert_eq!(answer.len(), 5);
+        assert_eq!(guess.len(), 5);
+        let answer: &[u8; 5] = answer.as_bytes().try_into().unwrap();
+        let guess: &[u8; 5] = guess.as_bytes().try_into().unwrap();
+
+        match mode {
+            ComputeMode::Nyt => Self::compute_n(answer, guess),
+            ComputeMode::AllOccurrences => {
+                let mut present = 0u32;
+                for &a in answer {
+                    present |= 1 << (a - b'a');
+                }
+                core::array::from_fn(|i| {
+                    if answer[i] == guess[i] {
+                        Correctness::Correct
+                    } else if present & (1 << (guess[i] - b'a')) != 0 {
+                        Correctness::Misplaced
+                    } else {
+                        Correctness::Wrong
+                    }
+                })
+            }
+        }
+    }
+
+    /// The mask of each of `guesses` against `answer`, in order, as [`Correctness::compute`]
+    /// gives them.
+    pub fn compute_all(answer: &str, guesses: &[&str]) -> Vec<[Self; 5]> {
+        guesses
+            .iter()
+            .map(|guess| Self::compute(answer, guess))
+            .collect()
+    }
+
+    /// Like [`Correctness::compute`], for words of any length `N`, given as lowercase ascii.
+    pub fn compute_n<const N: usize>(answer: &[u8; N], guess: &[u8; N]) -> [Self; N] {
+        let mut c = [Correctness::Wrong; N];
+
+        // Array indexed by lowercase ascii letters
+        let mut misplaced = [0u8; (b'z' - b'a' + 1) as usize];
+
+        // Find all correct letters
+        for ((&answer, &guess), c) in answer.iter().zip(guess).zip(c.iter_mut()) {
+            if answer == guess {
+                *c = Correctness::Correct
+            } else {
+                // If the letter does not match, count it as misplaced
+                misplaced[(answer - b'a') as usize] += 1;
+            }
+        }
+
+        // Check all of the non matching letters if they are misplaced
+        for (&guess, c) in guess.iter().zip(c.iter_mut()) {
+            // If the letter was guessed wrong and the same letter was counted as misplaced
+            if *c == Correctness::Wrong && misplaced[(guess - b'a') as usize] > 0 {
+                *c = Correctness::Misplaced;
+                misplaced[(guess - b'a') as usize] -= 1;
+            }
+        }
+        c
+    }
+
+    /// Same result as [`Correctness::compute`], computed without branching on the letters.
+    ///
+    /// Greens are found first, and every answer letter that isn't green is counted. Each
+    /// remaining guess letter is then yellow if its count is still positive, and uses one up.
+    pub fn compute_fast(answer: &str, guess: &str) -> [Self; 5] {
+        let answer: &[u8; 5] = answer.as_bytes().try_into().expect("answer is 5 letters");
+        let guess: &[u8; 5] = guess.as_bytes().try_into().expect("guess is 5 letters");
+
+        let green: [bool; 5] = core::array::from_fn(|i| answer[i] == guess[i]);
+        let mut counts = [0u8; 26];
+        for (&a, &g) in answer.iter().zip(&green) {
+            counts[usize::from(a - b'a')] += u8::from(!g);
+        }
+
+        const BY_CLASS: [Correctness; 3] = [
+            Correctness::Wrong,
+            Correctness::Misplaced,
+            Correctness::Correct,
+        ];
+        let mut c = [Correctness::Wrong; 5];
+        for ((&g, &green), c) in guess.iter().zip(&green).zip(&mut c) {
+            let count = &mut counts[usize::from(g - b'a')];
+            let yellow = !green & (*count > 0);
+            *count -= u8::from(yellow);
+            *c = BY_CLASS[usize::from(green) * 2 + usize::from(yellow)];
+        }
+        c
+    }
+
+    /// How many distinct masks there are, which is [`MAX_MASK_ENUM`].
+    pub const PATTERN_COUNT: usize = MAX_MASK_ENUM;
+
+    /// Every possible mask, in [`Correctness::pattern_index`] order.
+    pub fn patterns() -> impl Iterator<Item = [Self; 5]> {
+        (0..Self::PATTERN_COUNT).map(Self::pattern_from_index)
+    }
+
+    /// A dense index for `mask` in `0..PATTERN_COUNT`, for use with fixed-size arrays. This is
+    /// the same as `u8::from` of the [`PackedCorrectness`].
+    pub fn pattern_index(mask: &[Self; 5]) -> usize {
+        usize::from(u8::from(PackedCorrectness::from_array(mask)))
+    }
+
+    /// The inverse of [`Correctness::pattern_index`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below [`Correctness::PATTERN_COUNT`].
+    pub fn pattern_from_index(index: usize) -> [Self; 5] {
+        assert!(
+            index < Self::PATTERN_COUNT,
+            "pattern index {} out of range",
+            index
+        );
+        PackedCorrectness(NonZeroU8::new(index as u8 + 1).unwrap()).to_array()
+    }
+
+    /// Like [`Correctness::compute`], but packed into a single byte for cheap comparisons.
+    pub fn compute_packed(answer: &str, guess: &str) -> PackedCorrectness {
+        PackedCorrectness::from(Self::compute(answer, guess))
+    }
+
+    /// Like [`Correctness::compute`], along with what the mask says about how many times each
+    /// letter of the guess appears in the answer.
+    pub fn analyze(answer: &str, guess: &str) -> ([Self; 5], LetterInfo) {
+        let mask = Self::compute(answer, guess);
+        (mask, LetterInfo::new(guess.as_bytes(), &mask))
+    }
+}
+
+/// How many times each letter appears in the answer, as far as a single mask tells. See
+/// [`Correctness::analyze`].
+///
+/// Every green or yellow copy of a letter in the guess is one more copy in the answer, and a
+/// gray copy means there are no more than that. Only the letters `a` to `z` are counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LetterInfo {
+    /// The number of green or yellow copies of each letter.
+    min: [u8; 26],
+    /// One bit per letter that also had a gray copy.
+    exact: u32,
+}
+
+impl LetterInfo {
+    fn new(guess: &[u8], mask: &[Correctness; 5]) -> Self {
+        let mut info = Self {
+            min: [0; 26],
+            exact: 0,
+        };
+        for (&letter, c) in guess.iter().zip(mask) {
+            let Some(i) = Self::index(letter) else {
+                continue;
+            };
+            match c {
+                Correctness::Correct | Correctness::Misplaced => info.min[i] += 1,
+                Correctness::Wrong => info.exact |= 1 << i,
+            }
+        }
+        info
+    }
+
+    /// The position of `letter` in the alphabet, if it is one of `a` to `z`.
+    fn index(letter: u8) -> Option<usize> {
+        letter
+            .is_ascii_lowercase()
+            .then(|| usize::from(letter - b'a'))
+    }
+
+    /// The fewest times `letter` can appear in the answer. Letters that weren't guessed, or
+    /// were only gray, give `0`, as does anything other than `a` to `z`.
+    pub fn min_count(&self, letter: u8) -> u8 {
+        Self::index(letter).map_or(0, |i| self.min[i])
+    }
+
+    /// How many times `letter` appears in the answer, if the guess had enough copies of it to
+    /// tell, which it did if at least one of them is gray. Anything other than `a` to `z`
+    /// gives `None`.
+    pub fn exact_count(&self, letter: u8) -> Option<u8> {
+        let i = Self::index(letter)?;
+        (self.exact & (1 << i) != 0).then(|| self.min[i])
+    }
+}
+
+/// How a guess that repeats a letter gets colored, see [`Correctness::compute_with`].
+///
+/// The modes only disagree when the guess has more copies of a letter than the answer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum ComputeMode {
+    /// Like the NYT game: each copy of a letter in the answer accounts for one green or yellow
+    /// in the guess, greens first and then yellows from left to right. Extra copies are gray.
+    #[default]
+    Nyt,
+    /// Every copy of a letter the answer has is colored, yellow where it isn't green, however
+    /// many copies the answer has. Some Wordle clones play this way.
+    AllOccurrences,
+}
+
+pub const MAX_MASK_ENUM: usize = 3 * 3 * 3 * 3 * 3;
+
+/// A wrapper type for `[Correctness; 5]` packed into a single byte with a niche.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+// The NonZeroUB here lets the compiler know that we're not using the value `0`,
+// and that `0` can therefore be used to represent `None` for `Option<PackedCorrectness>`
+pub struct PackedCorrectness(NonZeroU8);
+
+impl From<[Correctness; 5]> for PackedCorrectness {
+    fn from(c: [Correctness; 5]) -> Self {
+        let packed = c.iter().fold(0, |acc, c| {
+            acc * 3
+                + match c {
+                    Correctness::Correct => 0,
+                    Correctness::Misplaced => 1,
+                    Correctness::Wrong => 2,
+                }
+        });
+        Self(NonZeroU8::new(packed + 1).unwrap())
+    }
+}
+
+impl From<PackedCorrectness> for u8 {
+    fn from(this: PackedCorrectness) -> Self {
+        this.0.get() - 1
+    }
+}
+
+impl PackedCorrectness {
+    /// Pack a mask. The encoding is stable: reading the mask as a base-3 number, most significant
+    /// position first, with `Correct = 0`, `Misplaced = 1` and `Wrong = 2`, gives `u8::from`
+    /// of the packed value.
+    pub fn from_array(mask: &[Correctness; 5]) -> Self {
+        Self::from(*mask)
+    }
+
+    pub fn to_array(self) -> [Correctness; 5] {
+        let mut packed = u8::from(self);
+        let mut mask = [Correctness::Correct; 5];
+        for c in mask.iter_mut().rev() {
+            *c = match packed % 3 {
+                0 => Correctness::Correct,
+                1 => Correctness::Misplaced,
+                _ => Correctness::Wrong,
+            };
+            packed /= 3;
+        }
+        mask
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess<'a> {
+    pub word: Cow<'a, str>,
+    pub mask: [Correctness; 5],
+}
+
+impl Guess<'_> {
+    /// Whether `word` could be the answer, that is, whether guessing [`Guess::word`] with
+    /// `word` as the answer gives [`Guess::mask`].
+    ///
+    /// The order matters: masks are not symmetric, so `word` guessed against an answer of
+    /// `self.word` would generally give a different mask. Yellows are reported at their
+    /// position in the guess (`crane` guessed for `trace` is `W C C M C`, but `trace` guessed
+    /// for `crane` is `M C C W C`). The greens, and how many copies of each letter are colored,
+    /// are the same either way.
+    pub fn matches(&self, word: &str) -> bool {
+        assert_eq!(word.len(), 5);
+        assert_eq!(self.word.len(), 5);
+
+        let mut used = [false; 5];
+
+        // Check correct letters
+        for (i, (a, g)) in word.bytes().zip(self.word.bytes()).enumerate() {
+            if a == g {
+                if self.mask[i] != Correctness::Correct {
+                    return false;
+                }
+                used[i] = true;
+            } else if self.mask[i] == Correctness::Correct {
+                return false;
+            }
+        }
+
+        // Check misplaced letters
+        for (g, e) in self.word.bytes().zip(self.mask.iter()) {
+            if *e == Correctness::Correct {
+                continue;
+            }
+            if Correctness::is_misplaced(g, word, &mut used) != (*e == Correctness::Misplaced) {
+                return false;
+            }
+        }
+
+        // The rest will be all correctly wrong letters
+        true
+    }
+
+    /// Same as [`Guess::matches`], but yellows are checked against a count of the letters not yet
+    /// accounted for rather than by searching `word` for each one. Greens are checked first, and
+    /// the first position that disagrees with the mask bails out.
+    ///
+    /// `matches` already bails out early too, so in practice the two run at about the same speed.
+    pub fn allows(&self, word: &str) -> bool {
+        let word: &[u8; 5] = word.as_bytes().try_into().expect("word is 5 letters");
+        let guess: &[u8; 5] = self.word.as_bytes().try_into().expect("guess is 5 letters");
+
+        let mut unused = [0u8; 26];
+        for ((&w, &g), &m) in word.iter().zip(guess).zip(&self.mask) {
+            let green = w == g;
+            if green != (m == Correctness::Correct) {
+                return false;
+            }
+            if !green {
+                unused[usize::from(w - b'a')] += 1;
+            }
+        }
+
+        for (&g, &m) in guess.iter().zip(&self.mask) {
+            if m == Correctness::Correct {
+                continue;
+            }
+            let count = &mut unused[usize::from(g - b'a')];
+            let yellow = *count > 0;
+            if yellow != (m == Correctness::Misplaced) {
+                return false;
+            }
+            *count -= u8::from(yellow);
+        }
+        true
+    }
+
+    /// Work out what the mask says about each letter once, so that checking many words against
+    /// this guess is cheaper. See [`PreparedGuess`].
+    pub fn prepare(&self) -> PreparedGuess {
+        let word: [u8; 5] = self.word.as_bytes().try_into().expect("guess is 5 letters");
+
+        // `compute` marks the earliest copies of a letter yellow, so no mask with a gray copy
+        // before a yellow copy of the same letter can come up, and no word matches it.
+        let mut grayed = 0u32;
+        let mut consistent = true;
+        for (&letter, &c) in word.iter().zip(&self.mask) {
+            let bit = 1 << (letter - b'a');
+            match c {
+                Correctness::Wrong => grayed |= bit,
+                Correctness::Misplaced => consistent &= grayed & bit == 0,
+                Correctness::Correct => {}
+            }
+        }
+
+        let letters = LetterInfo::new(&word, &self.mask);
+        let (mut present, mut absent, mut counted) = (0, 0, 0);
+        for &letter in &word {
+            let bit = 1 << (letter - b'a');
+            match (letters.min_count(letter), letters.exact_count(letter)) {
+                (0, _) => absent |= bit,
+                (1, None) => present |= bit,
+                _ => {
+                    present |= bit;
+                    counted |= bit;
+                }
+            }
+        }
+
+        PreparedGuess {
+            word,
+            green: self.mask.map(|c| c == Correctness::Correct),
+            letters,
+            present,
+            absent,
+            counted,
+            consistent,
+        }
+    }
+
+    /// One bit per letter (`a` is bit 0) that the answer can't have at all: letters whose every
+    /// copy in the guess is gray. A letter that is gray in one spot but green or yellow in
+    /// another only tells how many copies there are, so it isn't included.
+    pub fn absent_letters(&self) -> u32 {
+        let (mut gray, mut colored) = (0u32, 0u32);
+        for (letter, &c) in self.word.bytes().zip(&self.mask) {
+            let bit = 1 << (letter - b'a');
+            match c {
+                Correctness::Wrong => gray |= bit,
+                Correctness::Correct | Correctness::Misplaced => colored |= bit,
+            }
+        }
+        gray & !colored
+    }
+}
+
+/// The turn a guesser is being asked to play, counting from 1, given the guesses made so far.
+pub fn turn(history: &[Guess]) -> usize {
+    history.len() + 1
+}
+
+/// A [`Guess`] with what its mask says about each letter worked out up front, for checking many
+/// words against it. Made by [`Guess::prepare`].
+///
+/// [`PreparedGuess::allows`] gives the same answer as [`Guess::matches`]. Most words are settled
+/// by comparing a bitset of their letters with the letters the answer must and must not have;
+/// only letters that need an exact or repeated count are counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PreparedGuess {
+    word: [u8; 5],
+    green: [bool; 5],
+    letters: LetterInfo,
+    /// One bit per letter the answer has at least once.
+    present: u32,
+    /// One bit per letter the answer doesn't have.
+    absent: u32,
+    /// One bit per letter that `present` and `absent` alone can't settle.
+    counted: u32,
+    /// Whether any word can give this mask at all.
+    consistent: bool,
+}
+
+impl PreparedGuess {
+    /// Whether `word` could be the answer, like [`Guess::matches`].
+    pub fn allows(&self, word: &str) -> bool {
+        let word: &[u8; 5] = word.as_bytes().try_into().expect("word is 5 letters");
+        if !self.consistent {
+            return false;
+        }
+
+        let mut seen = 0u32;
+        for ((&w, &g), &green) in word.iter().zip(&self.word).zip(&self.green) {
+            if (w == g) != green {
+                return false;
+            }
+            seen |= 1 << (w - b'a');
+        }
+        if seen & self.present != self.present || seen & self.absent != 0 {
+            return false;
+        }
+
+        let mut counted = self.counted;
+        while counted != 0 {
+            let letter = b'a' + counted.trailing_zeros() as u8;
+            counted &= counted - 1;
+            let count = word.iter().filter(|&&w| w == letter).count() as u8;
+            let ok = match self.letters.exact_count(letter) {
+                Some(exact) => count == exact,
+                None => count >= self.letters.min_count(letter),
+            };
+            if !ok {
+                return false;
+            }
+        }
+        true
+    }
+}
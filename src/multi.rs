@@ -0,0 +1,131 @@
+use crate::{Correctness, Guess, Guesser, Wordle};
+use std::borrow::Cow;
+
+/// Several games of Wordle played at once with shared guesses, like Quordle.
+///
+/// Every guess is scored against each board that is still unsolved. A board is solved once it
+/// has been guessed, and from then on it takes no further part in the game.
+#[derive(Default)]
+pub struct MultiWordle {
+    wordle: Wordle,
+}
+
+impl MultiWordle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Play against all of `answers` at once, using [`Guesser::guess_multi`].
+    ///
+    /// Returns, for each answer in order, the guess number it was solved on, or `None` if it
+    /// was still unsolved after 32 guesses.
+    pub fn play_multi<G: Guesser>(
+        &self,
+        answers: &[&'static str],
+        mut guesser: G,
+    ) -> Vec<Option<usize>> {
+        let mut scores = vec![None; answers.len()];
+        let mut boards: Vec<Vec<Guess<'static>>> = vec![Vec::new(); answers.len()];
+
+        // Popoki allows at least 32 guesses.
+        for i in 1..=32 {
+            let active: Vec<usize> = (0..answers.len())
+                .filter(|&b| scores[b].is_none())
+                .collect();
+            if active.is_empty() {
+                break;
+            }
+            let histories: Vec<Vec<Guess<'static>>> =
+                active.iter().map(|&b| boards[b].clone()).collect();
+            let guess = guesser.guess_multi(&histories);
+            assert!(
+                self.wordle.contains(&guess),
+                "guess '{}' is not in the dictionary",
+                guess
+            );
+
+            for &b in &active {
+                let mask = Correctness::compute(answers[b], &guess);
+                if guess == answers[b] {
+                    scores[b] = Some(i);
+                }
+                boards[b].push(Guess {
+                    word: Cow::Owned(guess.clone()),
+                    mask,
+                });
+            }
+        }
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solver;
+
+    /// Works through a fixed list of words, and records how many boards it was shown each turn.
+    struct Scripted {
+        words: Vec<&'static str>,
+        shown: Vec<usize>,
+    }
+
+    impl Guesser for &mut Scripted {
+        fn guess(&mut self, _history: &[Guess]) -> String {
+            unreachable!("only used on multiple boards")
+        }
+
+        fn guess_multi(&mut self, boards: &[Vec<Guess>]) -> String {
+            self.shown.push(boards.len());
+            self.words[self.shown.len() - 1].to_string()
+        }
+    }
+
+    #[test]
+    fn scores_align_with_answers() {
+        let mut g = Scripted {
+            words: vec!["wrong", "night", "right", "sight"],
+            shown: Vec::new(),
+        };
+        let scores = MultiWordle::new().play_multi(&["sight", "right", "night"], &mut g);
+        assert_eq!(scores, [Some(4), Some(3), Some(2)]);
+        // Solved boards drop out.
+        assert_eq!(g.shown, [3, 3, 2, 1]);
+    }
+
+    #[test]
+    fn unsolved_boards() {
+        let scores = MultiWordle::new().play_multi(
+            &["right", "wrong"],
+            guesser!(|_history| { "right".to_string() }),
+        );
+        assert_eq!(scores, [Some(1), None]);
+    }
+
+    #[test]
+    fn default_follows_first_active_board() {
+        let mut seen = Vec::new();
+        let scores = MultiWordle::new().play_multi(&["wrong", "right"], |history: &[Guess]| {
+            seen.push(history.len());
+            ["wrong", "right"][seen.len() - 1].to_string()
+        });
+        assert_eq!(scores, [Some(1), Some(2)]);
+        // The second turn only shows the (unsolved) second board, which has one entry.
+        assert_eq!(seen, [0, 1]);
+    }
+
+    #[test]
+    fn solver_plays_every_board() {
+        let w = MultiWordle::new();
+        let quordle = w.play_multi(&["cigar", "humph", "awake", "rebut"], Solver::default());
+        let pair = w.play_multi(&["rebut", "sissy"], Solver::default());
+        // Quordle allows nine guesses.
+        for scores in [quordle, pair] {
+            assert!(
+                scores.iter().all(|s| s.is_some_and(|s| s <= 9)),
+                "{:?}",
+                scores
+            );
+        }
+    }
+}
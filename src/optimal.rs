@@ -0,0 +1,172 @@
+use crate::{Correctness, PackedCorrectness};
+use std::collections::HashMap;
+
+/// The largest candidate set the exact search will take on, since it is exponential in the
+/// number of candidates.
+pub const MAX_EXACT: usize = 12;
+
+/// The expected number of guesses, counting the final correct one, that optimal play needs to
+/// find an answer drawn uniformly from `remaining`, guessing from `allowed` or `remaining`.
+///
+/// This is found by exhaustive search, so it is only practical for small candidate sets.
+///
+/// # Panics
+///
+/// Panics if `remaining` is empty or has more than [`MAX_EXACT`] words.
+pub fn expected_optimal_guesses(remaining: &[&str], allowed: &[&str]) -> f64 {
+    let all: Vec<usize> = (0..remaining.len()).collect();
+    Search::new(remaining, allowed).solve(&all)
+}
+
+/// How many more guesses, in expectation, playing `chosen` costs compared to the best possible
+/// guess from `allowed` or `remaining`. Optimal play is assumed from the next turn onwards.
+///
+/// This is `0.0` when `chosen` is optimal, and infinite when `chosen` can neither be the answer
+/// nor tell any of the candidates apart.
+///
+/// # Panics
+///
+/// Panics if `remaining` is empty or has more than [`MAX_EXACT`] words.
+pub fn guess_regret(chosen: &str, remaining: &[&str], allowed: &[&str]) -> f64 {
+    let all: Vec<usize> = (0..remaining.len()).collect();
+    let mut search = Search::new(remaining, allowed);
+    search.cost(chosen, &all) - search.solve(&all)
+}
+
+/// The guess from `remaining` or `allowed` that needs the fewest guesses in expectation with
+/// optimal play afterwards, and that expectation. Ties go to the earliest word, looking at
+/// `remaining` first.
+///
+/// # Panics
+///
+/// Panics if `remaining` is empty or has more than [`MAX_EXACT`] words.
+pub(crate) fn optimal_guess<'w>(remaining: &[&'w str], allowed: &[&'w str]) -> (&'w str, f64) {
+    let all: Vec<usize> = (0..remaining.len()).collect();
+    let mut search = Search::new(remaining, allowed);
+    let mut best = (remaining[0], f64::INFINITY);
+    for &guess in remaining.iter().chain(allowed) {
+        let cost = search.cost(guess, &all);
+        if cost < best.1 {
+            best = (guess, cost);
+        }
+    }
+    best
+}
+
+struct Search<'a> {
+    remaining: &'a [&'a str],
+    allowed: &'a [&'a str],
+    /// Optimal expected guesses, keyed by (sorted) indices into `remaining`.
+    memo: HashMap<Vec<usize>, f64>,
+}
+
+impl<'a> Search<'a> {
+    fn new(remaining: &'a [&'a str], allowed: &'a [&'a str]) -> Self {
+        assert!(!remaining.is_empty(), "no candidates remain");
+        assert!(
+            remaining.len() <= MAX_EXACT,
+            "exact search is limited to {} candidates, got {}",
+            MAX_EXACT,
+            remaining.len()
+        );
+        Self {
+            remaining,
+            allowed,
+            memo: HashMap::new(),
+        }
+    }
+
+    fn solve(&mut self, set: &[usize]) -> f64 {
+        if set.len() == 1 {
+            return 1.0;
+        }
+        if let Some(&e) = self.memo.get(set) {
+            return e;
+        }
+
+        // Guessing a candidate that tells all the others apart can't be beaten.
+        let n = set.len() as f64;
+        let floor = (2.0 * n - 1.0) / n;
+
+        let mut best = f64::INFINITY;
+        let guesses = self
+            .allowed
+            .iter()
+            .chain(set.iter().map(|&i| &self.remaining[i]));
+        for &guess in guesses {
+            best = best.min(self.cost(guess, set));
+            if best <= floor {
+                break;
+            }
+        }
+
+        self.memo.insert(set.to_vec(), best);
+        best
+    }
+
+    /// Expected guesses if `guess` is played now and play is optimal afterwards.
+    fn cost(&mut self, guess: &str, set: &[usize]) -> f64 {
+        let mut patterns: Vec<(u8, usize)> = set
+            .iter()
+            .map(|&i| {
+                let mask = Correctness::compute(self.remaining[i], guess);
+                (u8::from(PackedCorrectness::from(mask)), i)
+            })
+            .collect();
+        patterns.sort_unstable();
+
+        let solved = u8::from(PackedCorrectness::from([Correctness::Correct; 5]));
+        let mut total = 0.0;
+        for bucket in patterns.chunk_by(|a, b| a.0 == b.0) {
+            if bucket[0].0 == solved {
+                continue;
+            }
+            if bucket.len() == set.len() {
+                // Learned nothing, and it wasn't the answer either.
+                return f64::INFINITY;
+            }
+            let members: Vec<usize> = bucket.iter().map(|&(_, i)| i).collect();
+            total += bucket.len() as f64 * self.solve(&members);
+        }
+        1.0 + total / set.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REMAINING: [&str; 3] = ["aaaaa", "aaaab", "aaabb"];
+
+    #[test]
+    fn single_candidate() {
+        assert_eq!(expected_optimal_guesses(&["right"], &[]), 1.0);
+    }
+
+    #[test]
+    fn splitting_candidate_is_optimal() {
+        // `aaaaa` wins a third of the time and tells the other two apart otherwise.
+        let e = expected_optimal_guesses(&REMAINING, &["bbbbb"]);
+        assert!((e - 5.0 / 3.0).abs() < 1e-9);
+        assert_eq!(guess_regret("aaaaa", &REMAINING, &["bbbbb"]), 0.0);
+    }
+
+    #[test]
+    fn probe_has_regret() {
+        // `bbbbb` splits all three apart, but can never win outright.
+        let regret = guess_regret("bbbbb", &REMAINING, &["bbbbb"]);
+        assert!((regret - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn useless_guess_is_infinitely_bad() {
+        assert!(guess_regret("zzzzz", &REMAINING, &[]).is_infinite());
+    }
+
+    #[test]
+    #[should_panic]
+    fn refuses_large_sets() {
+        let words = ["aaaaa"; MAX_EXACT + 1];
+        expected_optimal_guesses(&words, &[]);
+    }
+}
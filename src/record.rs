@@ -0,0 +1,83 @@
+use crate::{Correctness, Guess};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+
+/// An owned, serializable copy of a [`Guess`], for storing or sending game history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuessRecord {
+    #[serde(deserialize_with = "five_letters")]
+    pub word: String,
+    pub mask: [Correctness; 5],
+}
+
+fn five_letters<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let word = String::deserialize(deserializer)?;
+    if word.len() != 5 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(de::Error::invalid_value(
+            de::Unexpected::Str(&word),
+            &"a word of five lowercase ascii letters",
+        ));
+    }
+    Ok(word)
+}
+
+impl From<&Guess<'_>> for GuessRecord {
+    fn from(guess: &Guess<'_>) -> Self {
+        Self {
+            word: guess.word.to_string(),
+            mask: guess.mask,
+        }
+    }
+}
+
+impl From<GuessRecord> for Guess<'static> {
+    fn from(record: GuessRecord) -> Self {
+        Self {
+            word: Cow::Owned(record.word),
+            mask: record.mask,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let history = [
+            Guess {
+                word: Cow::Borrowed("trace"),
+                mask: mask![W M W W M],
+            },
+            Guess {
+                word: Cow::Borrowed("right"),
+                mask: mask![C C C C C],
+            },
+        ];
+        let records: Vec<GuessRecord> = history.iter().map(GuessRecord::from).collect();
+        let json = serde_json::to_string(&records).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"word":"trace","mask":["W","M","W","W","M"]},{"word":"right","mask":["C","C","C","C","C"]}]"#
+        );
+
+        let back: Vec<GuessRecord> = serde_json::from_str(&json).unwrap();
+        let back: Vec<Guess> = back.into_iter().map(Guess::from).collect();
+        assert_eq!(back, history);
+    }
+
+    #[test]
+    fn rejects_bad_words() {
+        for word in ["four", "sixsix", "RIGHT", "héllo"] {
+            let json = format!(r#"{{"word":"{}","mask":["C","C","C","C","C"]}}"#, word);
+            assert!(
+                serde_json::from_str::<GuessRecord>(&json).is_err(),
+                "{}",
+                word
+            );
+        }
+        let bad_mask = r#"{"word":"right","mask":["C","C","C","C","G"]}"#;
+        assert!(serde_json::from_str::<GuessRecord>(bad_mask).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use crate::Correctness;
+use std::fmt;
+
+/// Why a share grid could not be parsed. Rows are numbered from 1, ignoring blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridParseError {
+    /// A row did not have exactly five squares.
+    WrongLength { row: usize, got: usize },
+    /// A row contained something that isn't a square.
+    InvalidSquare { row: usize, found: char },
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { row, got } => {
+                write!(f, "row {} has {} squares, expected 5", row, got)
+            }
+            Self::InvalidSquare { row, found } => {
+                write!(f, "row {} contains '{}', which is not a square", row, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// Why a typed mask could not be parsed, see [`Correctness::from_chars`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMaskError {
+    /// The mask did not have exactly five characters.
+    WrongLength { got: usize },
+    /// The character at `index` (counting characters from 0) is not a color.
+    UnknownChar { index: usize, found: char },
+}
+
+impl fmt::Display for ParseMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { got } => write!(f, "mask has {} colors, expected 5", got),
+            Self::UnknownChar { index, found } => {
+                write!(f, "'{}' at position {} is not a color", found, index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMaskError {}
+
+impl Correctness {
+    /// Parse a mask typed as five color letters, like `gybbg`.
+    ///
+    /// `g` is green ([`Correctness::Correct`]), `y` is yellow ([`Correctness::Misplaced`]), and
+    /// either `b` (black) or `w` (white) is gray ([`Correctness::Wrong`]). Any case is accepted,
+    /// as is anything else [`parse_share_grid`] reads as a square.
+    pub fn from_chars(s: &str) -> Result<[Self; 5], ParseMaskError> {
+        let got = s.chars().count();
+        if got != 5 {
+            return Err(ParseMaskError::WrongLength { got });
+        }
+        let mut mask = [Correctness::Wrong; 5];
+        for ((index, c), m) in s.chars().enumerate().zip(mask.iter_mut()) {
+            *m = square(c).ok_or(ParseMaskError::UnknownChar { index, found: c })?;
+        }
+        Ok(mask)
+    }
+
+    /// The inverse of [`Correctness::from_chars`], using `G`, `Y` and `W`.
+    pub fn to_chars(mask: &[Self; 5]) -> String {
+        mask.iter()
+            .map(|c| match c {
+                Correctness::Correct => 'G',
+                Correctness::Misplaced => 'Y',
+                Correctness::Wrong => 'W',
+            })
+            .collect()
+    }
+}
+
+/// How [`render_mask`] and [`render_game`] draw each square.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Squares {
+    /// 🟩, 🟨 and ⬛, as in the grids people share.
+    #[default]
+    Emoji,
+    /// `G`, `Y` and `.`, for places that don't show emoji well.
+    Ascii,
+}
+
+impl Squares {
+    fn square(self, c: Correctness) -> char {
+        match (self, c) {
+            (Squares::Emoji, Correctness::Correct) => '🟩',
+            (Squares::Emoji, Correctness::Misplaced) => '🟨',
+            (Squares::Emoji, Correctness::Wrong) => '⬛',
+            (Squares::Ascii, Correctness::Correct) => 'G',
+            (Squares::Ascii, Correctness::Misplaced) => 'Y',
+            (Squares::Ascii, Correctness::Wrong) => '.',
+        }
+    }
+
+    /// One row of squares for `mask`.
+    pub fn render_mask(self, mask: &[Correctness; 5]) -> String {
+        mask.iter().map(|&c| self.square(c)).collect()
+    }
+
+    /// A share grid with one row per mask, oldest first, without a trailing newline.
+    pub fn render_game(self, masks: &[[Correctness; 5]]) -> String {
+        masks
+            .iter()
+            .map(|mask| self.render_mask(mask))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// `mask` as a row of emoji squares, like `🟩🟨⬛⬛🟩`. See [`Squares`] for other styles.
+pub fn render_mask(mask: &[Correctness; 5]) -> String {
+    Squares::Emoji.render_mask(mask)
+}
+
+/// The emoji share grid for a game that gave `masks`, one row per guess.
+///
+/// [`parse_share_grid`] reads it back.
+pub fn render_game(masks: &[[Correctness; 5]]) -> String {
+    Squares::Emoji.render_game(masks)
+}
+
+/// The full text people share after a game: a `Wordle 1,234 4/6` header, then a blank line,
+/// then the emoji grid from [`render_game`].
+///
+/// `score` is how many guesses the game took, or `None` if it was lost, which shows as `X`.
+/// `max_guesses` is the number after the slash, 6 in the real game, and hard mode games get a
+/// `*` after it.
+pub fn share_text(
+    puzzle_number: u32,
+    score: Option<usize>,
+    masks: &[[Correctness; 5]],
+    hard_mode: bool,
+    max_guesses: usize,
+) -> String {
+    let digits = puzzle_number.to_string();
+    let mut number = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            number.push(',');
+        }
+        number.push(digit);
+    }
+    let score = score.map_or("X".to_string(), |score| score.to_string());
+    let star = if hard_mode { "*" } else { "" };
+    format!(
+        "Wordle {} {}/{}{}\n\n{}",
+        number,
+        score,
+        max_guesses,
+        star,
+        render_game(masks)
+    )
+}
+
+fn square(c: char) -> Option<Correctness> {
+    match c {
+        '🟩' | 'G' | 'g' | 'C' | 'c' => Some(Correctness::Correct),
+        '🟨' | 'Y' | 'y' | 'M' | 'm' => Some(Correctness::Misplaced),
+        '⬜' | '⬛' | 'W' | 'w' | 'B' | 'b' | '.' => Some(Correctness::Wrong),
+        _ => None,
+    }
+}
+
+/// Turn a shared result grid back into the masks it was made from.
+///
+/// Each non-blank line is one guess. Squares may be emoji (🟩, 🟨, and either ⬜ or ⬛), the
+/// ascii `G`/`Y`/`W` colors (with `B` and `.` also accepted for gray), or `C`/`M`/`W` for
+/// correct, misplaced and wrong, in either case. [`Correctness::from_chars`] reads single rows
+/// the same way.
+pub fn parse_share_grid(s: &str) -> Result<Vec<[Correctness; 5]>, GridParseError> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            let row = i + 1;
+            let got = line.chars().count();
+            if got != 5 {
+                return Err(GridParseError::WrongLength { row, got });
+            }
+            let mut mask = [Correctness::Wrong; 5];
+            for (c, m) in line.chars().zip(mask.iter_mut()) {
+                *m = square(c).ok_or(GridParseError::InvalidSquare { row, found: c })?;
+            }
+            Ok(mask)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn emoji() {
+        let grid = "⬜🟨⬜⬜🟩\n🟩🟩⬛🟨⬜\n\n🟩🟩🟩🟩🟩\n";
+        assert_eq!(
+            parse_share_grid(grid),
+            Ok(vec![mask![W M W W C], mask![C C W M W], mask![C C C C C]])
+        );
+    }
+
+    #[test]
+    fn ascii() {
+        assert_eq!(
+            parse_share_grid("WYWWG\nggwyw"),
+            Ok(vec![mask![W M W W C], mask![C C W M W]])
+        );
+        assert_eq!(parse_share_grid("WWMGC"), Ok(vec![mask![W W M C C]]));
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(render_mask(&mask![C M W W C]), "🟩🟨⬛⬛🟩");
+        assert_eq!(Squares::Ascii.render_mask(&mask![C M W W C]), "GY..G");
+
+        let masks = [mask![W M W W C], mask![C C C C C]];
+        assert_eq!(render_game(&masks), "⬛🟨⬛⬛🟩\n🟩🟩🟩🟩🟩");
+        assert_eq!(Squares::Ascii.render_game(&masks), ".Y..G\nGGGGG");
+        assert_eq!(render_game(&[]), "");
+        for style in [Squares::Emoji, Squares::Ascii] {
+            assert_eq!(
+                parse_share_grid(&style.render_game(&masks)),
+                Ok(masks.to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn render_round_trips() {
+        for style in [Squares::Emoji, Squares::Ascii] {
+            for mask in Correctness::patterns() {
+                assert_eq!(Correctness::from_chars(&style.render_mask(&mask)), Ok(mask));
+            }
+        }
+    }
+
+    #[test]
+    fn share() {
+        let masks = [
+            mask![W M W W W],
+            mask![W W C M W],
+            mask![C C W C W],
+            mask![C C C C C],
+        ];
+        assert_eq!(
+            share_text(1234, Some(4), &masks, false, 6),
+            "Wordle 1,234 4/6\n\n⬛🟨⬛⬛⬛\n⬛⬛🟩🟨⬛\n🟩🟩⬛🟩⬛\n🟩🟩🟩🟩🟩"
+        );
+        assert_eq!(
+            share_text(7, None, &[mask![W W W W W]; 6], true, 6),
+            format!("Wordle 7 X/6*\n\n{}", ["⬛⬛⬛⬛⬛"; 6].join("\n"))
+        );
+        assert!(share_text(1_234_567, Some(1), &masks[3..], false, 8)
+            .starts_with("Wordle 1,234,567 1/8\n"));
+    }
+
+    #[test]
+    fn typed_masks() {
+        assert_eq!(Correctness::from_chars("gybbg"), Ok(mask![C M W W C]));
+        assert_eq!(Correctness::from_chars("GYWwB"), Ok(mask![C M W W W]));
+        // The same squares as a share grid.
+        assert_eq!(Correctness::from_chars("🟩🟨⬛⬜c"), Ok(mask![C M W W C]));
+        assert_eq!(Correctness::from_chars("Cm..g"), Ok(mask![C M W W C]));
+        assert_eq!(Correctness::to_chars(&mask![C M W W C]), "GYWWG");
+        for mask in Correctness::patterns() {
+            let typed = Correctness::to_chars(&mask);
+            assert_eq!(Correctness::from_chars(&typed), Ok(mask));
+            assert_eq!(Correctness::from_chars(&typed.to_lowercase()), Ok(mask));
+        }
+    }
+
+    #[test]
+    fn malformed_typed_masks() {
+        assert_eq!(
+            Correctness::from_chars("gyb"),
+            Err(ParseMaskError::WrongLength { got: 3 })
+        );
+        assert_eq!(
+            Correctness::from_chars("gybbgg"),
+            Err(ParseMaskError::WrongLength { got: 6 })
+        );
+        assert_eq!(
+            Correctness::from_chars(""),
+            Err(ParseMaskError::WrongLength { got: 0 })
+        );
+        assert_eq!(
+            Correctness::from_chars("gyxbg"),
+            Err(ParseMaskError::UnknownChar {
+                index: 2,
+                found: 'x'
+            })
+        );
+        // Positions count characters, not bytes.
+        assert_eq!(
+            Correctness::from_chars("🟩gy✗c"),
+            Err(ParseMaskError::UnknownChar {
+                index: 3,
+                found: '✗'
+            })
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            parse_share_grid("🟩🟩🟩🟩\n"),
+            Err(GridParseError::WrongLength { row: 1, got: 4 })
+        );
+        assert_eq!(
+            parse_share_grid("GGGGG\nGGXGG"),
+            Err(GridParseError::InvalidSquare { row: 2, found: 'X' })
+        );
+    }
+}
@@ -1,10 +1,24 @@
-use crate::{Correctness, Guess, Guesser, PackedCorrectness, DICTIONARY, MAX_MASK_ENUM};
+#[cfg(feature = "compute-cache")]
+use crate::ComputeCache;
+use crate::{
+    dictionary, optimal::optimal_guess, partition, Constraints, Correctness, DictionaryError,
+    Guess, Guesser, PackedCorrectness, DICTIONARY, MAX_EXACT, MAX_MASK_ENUM,
+};
 use once_cell::sync::OnceCell;
 use once_cell::unsync::OnceCell as UnSyncOnceCell;
-use std::{borrow::Cow, cell::Cell};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    cmp::{Ordering, Reverse},
+    collections::{HashMap, HashSet},
+    fmt,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// The initial set of words after applying sigmoid smoothing
-static INITIAL_SIGMOID: OnceCell<Vec<(&'static str, f64, usize)>> = OnceCell::new();
+static INITIAL_SIGMOID: OnceCell<Vec<Entry>> = OnceCell::new();
 
 /// A per-thread cache of cached `Correctness` for each word pair.
 ///
@@ -16,20 +30,95 @@ type Cache = [[Cell<Option<PackedCorrectness>>; DICTIONARY.len()]; DICTIONARY.le
 thread_local! {
     static COMPUTES: UnSyncOnceCell<Box<Cache>> = Default::default();
 }
+#[derive(Clone)]
 pub struct Solver {
-    remaining: Cow<'static, Vec<(&'static str, f64, usize)>>,
+    /// Every word, weighted as configured, in the same order as the dictionary.
+    all: Cow<'static, [Entry]>,
+    remaining: Cow<'static, [Entry]>,
     entropy: Vec<f64>,
     options: Options,
     last_guess_idx: Option<usize>,
+    /// How many history entries have been applied to `remaining` so far.
+    seen: usize,
+    /// One bit per letter the answer is known not to have, see [`Guess::absent_letters`].
+    absent: u32,
+    rejected: Option<Vec<(Word, usize)>>,
+    best_opener: OnceCell<Word>,
+    /// Whether [`Options::excluded_letters`] had to be given up on this game.
+    used_excluded: bool,
+}
+
+/// Rates how nice a mask looks in a share grid, higher being nicer, see [`Options::aesthetic`].
+pub type MaskScorer = fn(&[Correctness; 5]) -> i32;
+
+/// Every word of a custom dictionary with how often it occurs, most common first, see
+/// [`Options::dictionary`].
+pub type WordCounts = Arc<[(Box<str>, usize)]>;
+
+/// A candidate: the word, its (possibly smoothed) weight, and its index in the dictionary.
+type Entry = (Word, f64, usize);
+
+/// A dictionary word, held inline so that candidates don't borrow from the dictionary, which
+/// [`Options`] owns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Word([u8; 5]);
+
+impl Word {
+    /// # Panics
+    ///
+    /// Panics if `word` isn't five bytes long, which dictionary words always are.
+    fn new(word: &str) -> Self {
+        Self(
+            word.as_bytes()
+                .try_into()
+                .expect("dictionary words are five letters"),
+        )
+    }
+}
+
+impl Deref for Word {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        // SAFETY: the bytes were copied whole from a `str` in `Word::new`.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+/// What every candidate scored on a turn is measured against.
+struct Turn {
+    /// Guesses made so far.
+    score: f64,
+    remaining_p: f64,
+    remaining_entropy: f64,
+    /// What to rank by, which exploring overrides.
+    rank_by: Rank,
 }
 
 #[derive(Debug, Copy, Clone)]
 struct Candidate {
-    word: &'static str,
+    word: Word,
     goodness: f64,
+    /// The word's weight and frequency, for breaking ties.
+    weight: f64,
+    count: usize,
     idx: usize,
 }
 
+impl Candidate {
+    /// Orders candidates from worst to best: by goodness, then, between equally good words, by
+    /// weight, then by raw frequency, and finally preferring the alphabetically first. This
+    /// doesn't depend on the order the words were scanned in.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.goodness
+            .total_cmp(&other.goodness)
+            .then(self.weight.total_cmp(&other.weight))
+            .then(self.count.cmp(&other.count))
+            .then(other.word.cmp(&self.word))
+    }
+}
+
 impl Default for Solver {
     fn default() -> Self {
         Options::default().build()
@@ -44,12 +133,24 @@ fn est_steps_left(entropy: f64) -> f64 {
 
 const PRINT_ESTIMATION: bool = false;
 
+/// The number of guesses a regular game of Wordle allows.
+pub const BUDGET: usize = 6;
+
+/// How many words the solver scans between calls to a progress callback.
+const PROGRESS_EVERY: usize = 1024;
+
+/// Exploration ranks the whole dictionary, so only do it once the candidates are few.
+const EXPLORE_MAX: usize = 32;
+
 const L: f64 = 1.0;
 const K: f64 = 30_000_000.0;
 const X0: f64 = 0.000_004_97;
 
-fn sigmoid(p: f64) -> f64 {
-    L / (1.0 + (-K * (p - X0)).exp())
+/// Smooth a word's share of all occurrences, so that common words are preferred without raw
+/// counts dominating: words well above `center` all weigh about the same, and words well below
+/// it hardly count. `steepness` sets how sharp the transition is.
+fn sigmoid(p: f64, center: f64, steepness: f64) -> f64 {
+    L / (1.0 + (-steepness * (p - center)).exp())
 }
 const PRINT_SIGMOID: bool = false;
 
@@ -72,64 +173,360 @@ pub enum Rank {
     ExpectedInformation,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct Options {
     /// If true, counts will be smoothed using a sigmoid
     pub sigmoid: bool,
 
+    /// The share of all word occurrences at which the sigmoid gives a word half weight.
+    pub sigmoid_center: f64,
+
+    /// How sharply the sigmoid goes from low to full weight around its center.
+    pub sigmoid_steepness: f64,
+
     /// If true, candidates will be ranked based on expected score
     pub rank_by: Rank,
 
+    /// Added to a word's score for each unit of probability that it is the answer, so that a
+    /// guess that might win outright beats one that only gives the same information. `0.0` by
+    /// default.
+    ///
+    /// [`Rank::ExpectedScore`] already counts the chance of winning, so this is mostly for the
+    /// information-based ranks, where a word's score is otherwise measured in bits.
+    pub solve_bonus: f64,
+
     /// If true, correctness computation will be cached
     pub cache: bool,
 
+    /// The correctness of every pair of words, worked out up front, to look masks up in rather
+    /// than computing them. Unlike [`Options::cache`], it is shared between threads and used by
+    /// the parallel scan. A table that doesn't cover the dictionary word for word is ignored.
+    /// See [`Options::with_compute_cache`].
+    #[cfg(feature = "compute-cache")]
+    pub compute_cache: Option<Arc<ComputeCache>>,
+
+    /// If set, once this many candidates or fewer remain, the next guess is found by exhaustive
+    /// search for the fewest guesses in expectation (see [`expected_optimal_guesses`]) rather
+    /// than by [`Options::rank_by`]. Every candidate counts as equally likely in the search.
+    ///
+    /// The search is exponential in the number of candidates, so thresholds above
+    /// [`MAX_EXACT`] act as [`MAX_EXACT`]. In hard mode only candidates are tried as guesses.
+    ///
+    /// [`expected_optimal_guesses`]: crate::expected_optimal_guesses
+    pub endgame: Option<usize>,
+
     /// If true, only the most likely 1/3 of candidates are considered at each step.
     pub cutoff: bool,
 
     /// If true, solver may not guess known-wrong words
     pub hard_mode: bool,
+
+    /// If set, a hard-mode solver plays for information rather than the answer when it can
+    /// afford to. See [`Options::with_exploration`].
+    pub exploration: Option<usize>,
+
+    /// If true, of the anagrams that are bound to get the same mask from every remaining
+    /// candidate, only the one that would win a tie is scored, which saves work without
+    /// changing the guess. [`Solver::rank`] leaves the others out.
+    ///
+    /// That is the case when the anagrams only differ in where they put letters that no
+    /// candidate has, which are gray wherever they are. A candidate has all of its own letters,
+    /// so this never collapses candidates, only probes, and makes no difference in hard mode.
+    pub dedup_anagrams: bool,
+
+    /// If true, every word removed from the candidate set is recorded along with the index of
+    /// the history entry that rejected it. See [`Solver::rejected`].
+    pub log_rejected: bool,
+
+    /// The word to open with, rather than scoring every word for the first guess, which takes a
+    /// while. `trace` by default, see [`Solver::best_opener`] and [`Options::with_opener`].
+    pub opener: Option<&'static str>,
+
+    /// Letters to keep out of every guess, by position in the alphabet. Words with any of them
+    /// are neither played nor counted as candidates, until the history rules out every other
+    /// word, see [`Solver::used_excluded_letters`]. See [`Options::with_excluded_letters`].
+    pub excluded_letters: [bool; 26],
+
+    /// For fun: among the words that score within `epsilon` of the best guess, play the one
+    /// whose mask the `scorer` rates highest, on average over the remaining candidates (counted
+    /// as likely as their weight). See [`Options::with_aesthetic`].
+    ///
+    /// Only the choice between scored words changes, so in hard mode the guess can still be
+    /// the answer, and a game that is down to one candidate plays it as usual. Every word is
+    /// scored a second time for this, and [`Solver::guess_within`] leaves it out.
+    pub aesthetic: Option<(f64, MaskScorer)>,
+
+    /// The second guess to play for each mask the opener can get, as made by
+    /// [`Solver::precompute_openings`], so that it doesn't have to be worked out. Only used
+    /// when the first guess was the opener. See [`Options::with_openings`].
+    pub openings: Option<Arc<HashMap<[Correctness; 5], String>>>,
+
+    /// The word to play once no word in the dictionary fits the history, as happens when the
+    /// answer isn't in it. Once it has been played, or if it is `None`, the first word of the
+    /// dictionary that hasn't been played yet is used instead. See [`Options::with_fallback`].
+    pub fallback: Option<&'static str>,
+
+    /// The words to play with, and how often each occurs, instead of the bundled dictionary.
+    /// Shared by every solver built from these options. See [`Options::with_dictionary`].
+    pub dictionary: Option<WordCounts>,
+
+    /// The only words that can be the answer, if not every word in the dictionary can. The
+    /// others are still played when they narrow things down. See [`Options::with_lists`].
+    pub answers: Option<Arc<[Box<str>]>>,
+
+    /// If true, candidates are scored on all cores. The chosen guess is the same either way.
+    ///
+    /// The parallel scan doesn't use [`Options::cache`] (only [`Options::compute_cache`]), and
+    /// only reports progress once it is done.
+    #[cfg(feature = "parallel")]
+    pub parallel: bool,
 }
 
 impl Default for Options {
     fn default() -> Self {
         Self {
             sigmoid: true,
+            sigmoid_center: X0,
+            sigmoid_steepness: K,
             rank_by: Rank::ExpectedScore,
+            solve_bonus: 0.0,
+            endgame: None,
             cache: true,
+            #[cfg(feature = "compute-cache")]
+            compute_cache: None,
             cutoff: true,
             hard_mode: true,
+            exploration: None,
+            dedup_anagrams: false,
+            log_rejected: false,
+            opener: Some("trace"),
+            excluded_letters: [false; 26],
+            aesthetic: None,
+            openings: None,
+            fallback: None,
+            dictionary: None,
+            answers: None,
+            #[cfg(feature = "parallel")]
+            parallel: true,
         }
     }
 }
 
 impl Options {
-    pub fn build(self) -> Solver {
-        let remaining = INITIAL_SIGMOID.get_or_init(|| {
-            let sum: usize = DICTIONARY.iter().map(|(_, count)| count).sum();
-
-            if PRINT_SIGMOID {
-                for &(word, count) in DICTIONARY.iter().rev() {
-                    let p = count as f64 / sum as f64;
-                    println!(
-                        "{} {:.6}% -> {:.6}% ({})",
-                        word,
-                        100.0 * p,
-                        100.0 * sigmoid(p),
-                        count
-                    );
-                }
+    /// Let the solver explore when it is far ahead of the budget.
+    ///
+    /// In hard mode the solver only ever guesses words that could still be the answer, and
+    /// with the cutoff it mostly tries the likeliest ones, which can leave it guessing one
+    /// candidate at a time through families like `_atch`. With exploration enabled, whenever
+    /// between 3 and 32 candidates remain and at least `turns_slack + 2` of the [`BUDGET`]
+    /// turns are left (enough for a probe, the answer, and `turns_slack` spare turns), the
+    /// solver instead scores every word that fits the clues so far, and plays the one that
+    /// tells the most about the answer, as with [`Rank::ExpectedInformation`].
+    ///
+    /// The probe still fits every clue, so the game stays within hard mode's rules.
+    pub fn with_exploration(mut self, turns_slack: usize) -> Self {
+        self.exploration = Some(turns_slack);
+        self
+    }
+
+    /// Open with `word`, after checking that it is in the dictionary.
+    ///
+    /// Setting [`Options::opener`] directly skips the check, and an opener that isn't in the
+    /// dictionary is then quietly ignored, as it is if the dictionary is changed afterwards.
+    pub fn with_opener(mut self, word: &'static str) -> Result<Self, UnknownOpener> {
+        if !self.words().any(|(w, _)| w == word) {
+            return Err(UnknownOpener { opener: word });
+        }
+        self.opener = Some(word);
+        Ok(self)
+    }
+
+    /// Play `word` when no word fits the history, after checking that it is in the
+    /// dictionary. See [`Options::fallback`].
+    pub fn with_fallback(mut self, word: &'static str) -> Result<Self, UnknownFallback> {
+        if !self.words().any(|(w, _)| w == word) {
+            return Err(UnknownFallback { fallback: word });
+        }
+        self.fallback = Some(word);
+        Ok(self)
+    }
+
+    /// Look masks up in a [`ComputeCache`] of the dictionary rather than computing them. The
+    /// table is built here, which takes a while and about 170MB for the bundled dictionary, and
+    /// is shared by every solver built from these options (and their clones).
+    ///
+    /// [`Options::with_dictionary`] and [`Options::with_lists`] already do this for the words
+    /// they load when [`Options::cache`] is set, and otherwise drop the table, since it is laid
+    /// out for the dictionary it was built for.
+    #[cfg(feature = "compute-cache")]
+    pub fn with_compute_cache(mut self) -> Self {
+        let words: Vec<&str> = self.words().map(|(word, _)| word).collect();
+        self.compute_cache = Some(Arc::new(ComputeCache::build(&words)));
+        self
+    }
+
+    /// Never guess a word with any letter set in `letters`, where `letters[0]` is `a`, if it
+    /// can be helped. An opener with one of them is skipped, and the first guess is then scored
+    /// like any other.
+    pub fn with_excluded_letters(mut self, letters: [bool; 26]) -> Self {
+        self.excluded_letters = letters;
+        self
+    }
+
+    /// Prefer pretty share grids, as rated by `scorer`, over guesses that are at most
+    /// `epsilon` worse. See [`Options::aesthetic`].
+    pub fn with_aesthetic(mut self, epsilon: f64, scorer: MaskScorer) -> Self {
+        self.aesthetic = Some((epsilon, scorer));
+        self
+    }
+
+    /// Look second guesses up in `table`, from [`Solver::precompute_openings`] with the same
+    /// options, rather than working them out, after checking that every word in it is in the
+    /// dictionary.
+    ///
+    /// Setting [`Options::openings`] directly skips the check, and second guesses that aren't
+    /// in the dictionary are then worked out as usual.
+    pub fn with_openings(
+        mut self,
+        table: HashMap<[Correctness; 5], String>,
+    ) -> Result<Self, UnknownOpening> {
+        let words: HashSet<&str> = self.words().map(|(word, _)| word).collect();
+        if let Some((&mask, word)) = table
+            .iter()
+            .find(|(_, word)| !words.contains(word.as_str()))
+        {
+            return Err(UnknownOpening {
+                mask,
+                word: word.clone(),
+            });
+        }
+        self.openings = Some(Arc::new(table));
+        Ok(self)
+    }
+
+    /// Play with the words of a custom dictionary of `word frequency` lines, like the bundled
+    /// one, instead. Blank lines are skipped.
+    ///
+    /// The thread-local correctness cache only covers the bundled dictionary, so if
+    /// [`Options::cache`] is set, a [`ComputeCache`] is built for the new one instead, which
+    /// takes a byte per pair of words. Without the `compute-cache` feature, masks are always
+    /// computed with a custom dictionary.
+    pub fn with_dictionary(self, text: &str) -> Result<Self, DictionaryError> {
+        let words = dictionary::parse(text, 5)?;
+        Ok(self.with_words(words, None))
+    }
+
+    /// Like [`Options::with_dictionary`], but with separate lists of the words that may be
+    /// guessed and the words that may be the answer, as in the real game. Candidates are only
+    /// ever drawn from `answers`, while any word in either list can be played to narrow them
+    /// down.
+    ///
+    /// Words in `answers` that aren't in `guessable` are added to it, along with their
+    /// frequency.
+    pub fn with_lists(self, guessable: &str, answers: &str) -> Result<Self, DictionaryError> {
+        let mut words = dictionary::parse(guessable, 5)?;
+        let answers = dictionary::parse(answers, 5)?;
+        let known: HashSet<&str> = words.iter().map(|&(word, _)| word).collect();
+        let missing: Vec<_> = answers
+            .iter()
+            .filter(|(word, _)| !known.contains(word))
+            .cloned()
+            .collect();
+        words.extend(missing);
+        let answers = answers.into_iter().map(|(word, _)| word).collect();
+        Ok(self.with_words(words, Some(answers)))
+    }
+
+    /// Play with `words`, and only `answers` as the answer if given, replacing any
+    /// [`Options::compute_cache`] with one for the new words if [`Options::cache`] is set.
+    fn with_words(mut self, mut words: Vec<(&str, usize)>, answers: Option<Vec<&str>>) -> Self {
+        // The cutoff expects the most common words first.
+        words.sort_by_key(|&(_, count)| Reverse(count));
+        self.dictionary = Some(
+            words
+                .into_iter()
+                .map(|(word, count)| (word.into(), count))
+                .collect(),
+        );
+        self.answers = answers.map(|answers| answers.into_iter().map(Box::from).collect());
+        #[cfg(feature = "compute-cache")]
+        {
+            self.compute_cache = None;
+            if self.cache {
+                self = self.with_compute_cache();
             }
+        }
+        self
+    }
 
-            DICTIONARY
-                .iter()
-                .copied()
+    /// The words to play with, and how often each occurs, most common first.
+    pub(crate) fn words(&self) -> Box<dyn Iterator<Item = (&str, usize)> + '_> {
+        match &self.dictionary {
+            Some(dictionary) => Box::new(dictionary.iter().map(|(word, count)| (&**word, *count))),
+            None => Box::new(DICTIONARY.iter().copied()),
+        }
+    }
+
+    pub fn build(self) -> Solver {
+        let all = self.weights();
+        self.build_with(all)
+    }
+
+    /// Every word in the dictionary with its weight and index, most common first.
+    fn weights(&self) -> Cow<'static, [Entry]> {
+        let weigh = |weight: &dyn Fn(f64) -> f64| -> Vec<Entry> {
+            let sum: usize = self.words().map(|(_, count)| count).sum();
+            self.words()
                 .enumerate()
-                .map(|(idx, (word, count))| (word, sigmoid(count as f64 / sum as f64), idx))
+                .map(|(idx, (word, count))| {
+                    (Word::new(word), weight(count as f64 / sum as f64), idx)
+                })
                 .collect()
-        });
+        };
+        if !self.sigmoid {
+            Cow::Owned(weigh(&|p| p))
+        } else if self.dictionary.is_none()
+            && self.sigmoid_center == X0
+            && self.sigmoid_steepness == K
+        {
+            Cow::Borrowed(&**INITIAL_SIGMOID.get_or_init(|| {
+                if PRINT_SIGMOID {
+                    let sum: usize = DICTIONARY.iter().map(|(_, count)| count).sum();
+                    for &(word, count) in DICTIONARY.iter().rev() {
+                        let p = count as f64 / sum as f64;
+                        println!(
+                            "{} {:.6}% -> {:.6}% ({})",
+                            word,
+                            100.0 * p,
+                            100.0 * sigmoid(p, X0, K),
+                            count
+                        );
+                    }
+                }
+                weigh(&|p| sigmoid(p, X0, K))
+            }))
+        } else {
+            Cow::Owned(weigh(&|p| {
+                sigmoid(p, self.sigmoid_center, self.sigmoid_steepness)
+            }))
+        }
+    }
 
-        if self.cache {
+    /// Build a solver playing with `all`, as worked out by [`Options::weights`].
+    fn build_with(self, all: Cow<'static, [Entry]>) -> Solver {
+        // The cache is laid out for the bundled dictionary.
+        let mut options = self;
+        #[cfg(feature = "compute-cache")]
+        if options
+            .compute_cache
+            .as_ref()
+            .is_some_and(|table| table.len() != all.len())
+        {
+            options.compute_cache = None;
+        }
+        options.cache &= options.dictionary.is_none();
+        if options.cache {
             COMPUTES.with(|c| {
                 c.get_or_init(|| {
                     let c = &Cell::new(None::<PackedCorrectness>);
@@ -152,15 +549,67 @@ impl Options {
                 });
             });
         }
-        Solver {
-            remaining: Cow::Borrowed(remaining),
+        let mut solver = Solver {
+            remaining: Cow::Borrowed(&[]),
+            all,
             entropy: Vec::new(),
             last_guess_idx: None,
-            options: self,
-        }
+            seen: 0,
+            absent: 0,
+            rejected: options.log_rejected.then(Vec::new),
+            best_opener: OnceCell::new(),
+            used_excluded: false,
+            options,
+        };
+        solver.remaining = solver.starting_candidates();
+        solver
+    }
+}
+
+/// An opener passed to [`Options::with_opener`] that isn't in the dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownOpener {
+    pub opener: &'static str,
+}
+
+impl fmt::Display for UnknownOpener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "opener '{}' is not in the dictionary", self.opener)
+    }
+}
+
+impl std::error::Error for UnknownOpener {}
+
+/// A fallback passed to [`Options::with_fallback`] that isn't in the dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFallback {
+    pub fallback: &'static str,
+}
+
+impl fmt::Display for UnknownFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fallback '{}' is not in the dictionary", self.fallback)
+    }
+}
+
+impl std::error::Error for UnknownFallback {}
+
+/// A second guess passed to [`Options::with_openings`] that isn't in the dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownOpening {
+    /// The mask of the opener it follows.
+    pub mask: [Correctness; 5],
+    pub word: String,
+}
+
+impl fmt::Display for UnknownOpening {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "second guess '{}' is not in the dictionary", self.word)
     }
 }
 
+impl std::error::Error for UnknownOpening {}
+
 // This inline gives about 13% speedup.
 #[inline]
 fn get_packed(
@@ -173,7 +622,7 @@ fn get_packed(
     match cell.get() {
         Some(a) => a,
         None => {
-            let correctness = PackedCorrectness::from(Correctness::compute(answer, guess));
+            let correctness = Correctness::compute_packed(answer, guess);
             cell.set(Some(correctness));
             correctness
         }
@@ -184,59 +633,536 @@ impl Solver {
     pub fn builder() -> Options {
         Options::default()
     }
+
+    /// A solver with the same options that hasn't seen any guesses, like [`Guesser::reset`]
+    /// but leaving `self` alone.
+    ///
+    /// The weighted word list is shared rather than worked out again, and with the default
+    /// options so is the starting candidate set, which makes this much cheaper than
+    /// [`Options::build`] for handing each of many games its own solver. Cloning a solver
+    /// instead keeps what it has learned, which costs a copy of its remaining candidates once
+    /// it has any.
+    pub fn fresh(&self) -> Solver {
+        let mut solver = self.options.clone().build_with(self.all.clone());
+        solver.best_opener = self.best_opener.clone();
+        solver
+    }
+
+    /// The second guess for every mask the opener can get, for loading back with
+    /// [`Options::with_openings`] so that later solvers skip working it out.
+    ///
+    /// Every guess is computed live, ignoring any table `self` already has. The table is
+    /// empty without an [`Options::opener`] in the dictionary, since there is then no fixed
+    /// first guess to plan after.
+    pub fn precompute_openings(&self) -> HashMap<[Correctness; 5], String> {
+        let mut live = self.fresh();
+        live.options.openings = None;
+        let mut table = HashMap::new();
+        let opener = match self.options.opener {
+            Some(opener) if self.all.iter().any(|(w, _, _)| **w == *opener) => opener,
+            _ => return table,
+        };
+        let masks: HashSet<_> = live
+            .remaining
+            .iter()
+            .map(|(answer, _, _)| Correctness::compute(answer, opener))
+            .collect();
+        for mask in masks {
+            if mask == [Correctness::Correct; 5] {
+                continue;
+            }
+            let mut solver = live.fresh();
+            solver.guess(&[]);
+            let history = [Guess {
+                word: Cow::Borrowed(opener),
+                mask,
+            }];
+            table.insert(mask, solver.guess(&history));
+        }
+        table
+    }
+
+    /// Whether [`Options::excluded_letters`] had to be given up on this game, because no word
+    /// without them fits the history, so the answer must have one. From then on the solver
+    /// plays as if no letters were excluded.
+    pub fn used_excluded_letters(&self) -> bool {
+        self.used_excluded
+    }
+
+    /// The words removed from the candidate set so far, each paired with the index of the
+    /// history entry that rejected it. Words ruled out by hints (see [`Guesser::reveal`]) are
+    /// not included.
+    ///
+    /// Returns `None` unless [`Options::log_rejected`] was set.
+    pub fn rejected(&self) -> Option<Vec<(&str, usize)>> {
+        let rejected = self.rejected.as_ref()?;
+        Some(rejected.iter().map(|(word, by)| (&**word, *by)).collect())
+    }
+
+    /// Every word in the dictionary, in order.
+    pub(crate) fn words(&self) -> impl Iterator<Item = &str> {
+        self.all.iter().map(|(word, _, _)| &**word)
+    }
+
+    /// How often the word at `idx` in the dictionary occurs.
+    fn count(&self, idx: usize) -> usize {
+        match &self.options.dictionary {
+            Some(dictionary) => dictionary[idx].1,
+            None => DICTIONARY[idx].1,
+        }
+    }
 }
 
 impl Solver {
-    fn trim(&mut self, mut cmp: impl FnMut(&str, usize) -> bool) {
-        if matches!(self.remaining, Cow::Owned(_)) {
-            self.remaining
-                .to_mut()
-                .retain(|&(word, _, word_idx)| cmp(word, word_idx));
-        } else {
-            self.remaining = Cow::Owned(
-                self.remaining
-                    .iter()
-                    .filter(|(word, _, word_idx)| cmp(word, *word_idx))
-                    .copied()
-                    .collect(),
-            );
+    fn trim(&mut self, by: usize, mut cmp: impl FnMut(&Word, usize) -> bool) {
+        // Keep the logging branch outside of the per-candidate closure so that there's no
+        // overhead when no one is listening.
+        match &mut self.rejected {
+            Some(rejected) => retain(&mut self.remaining, |word, word_idx| {
+                let keep = cmp(word, word_idx);
+                if !keep {
+                    rejected.push((*word, by));
+                }
+                keep
+            }),
+            None => retain(&mut self.remaining, cmp),
         }
     }
 }
 
-impl Guesser for Solver {
-    fn guess(&mut self, history: &[Guess]) -> String {
-        let score = history.len() as f64;
-
-        if let Some(last) = history.last() {
-            if self.options.cache {
-                let reference = PackedCorrectness::from(last.mask);
-                COMPUTES.with(|c| {
-                    let row = &c.get().unwrap()[self.last_guess_idx.unwrap()];
-                    self.trim(|word, word_idx| {
-                        reference == get_packed(row, &last.word, word, word_idx)
-                    });
-                });
-            } else {
-                self.trim(|word, _| last.matches(word));
-            }
+/// One bit per letter in `word`, `a` being bit 0.
+fn letter_bits(word: &str) -> u32 {
+    word.bytes()
+        .fold(0, |bits, letter| bits | 1 << (letter - b'a'))
+}
+
+fn retain(remaining: &mut Cow<'static, [Entry]>, mut keep: impl FnMut(&Word, usize) -> bool) {
+    if matches!(remaining, Cow::Owned(_)) {
+        remaining
+            .to_mut()
+            .retain(|(word, _, word_idx)| keep(word, *word_idx));
+    } else {
+        *remaining = Cow::Owned(
+            remaining
+                .iter()
+                .filter(|(word, _, word_idx)| keep(word, *word_idx))
+                .copied()
+                .collect(),
+        );
+    }
+}
+
+impl Solver {
+    /// Like [`Guesser::guess`], but reports progress through the candidate scan, which can take
+    /// a while when many candidates remain.
+    ///
+    /// `on_progress(processed, total)` is called after every 1024 words scanned, and once more
+    /// with `processed == total` when the scan ends (which may be early because of the cutoff).
+    /// It is not called at all for guesses that need no scan, like the opener.
+    pub fn guess_with_progress(
+        &mut self,
+        history: &[Guess],
+        on_progress: impl FnMut(usize, usize),
+    ) -> String {
+        self.guess_until(history, on_progress, None)
+    }
+
+    /// Like [`Guesser::guess`], but stops scoring words once `budget` has passed and plays the
+    /// best one scored so far. At least one word is always scored, so even a zero budget gives
+    /// a dictionary word, and in hard mode one that could be the answer.
+    ///
+    /// Words are scored most frequent first, so the early ones are usually the likeliest
+    /// answers and among the better guesses. Guesses that need no scan, like the opener or an
+    /// [`Options::endgame`] search, are made as usual. The parallel scan is not used.
+    pub fn guess_within(&mut self, history: &[Guess], budget: Duration) -> String {
+        let deadline = Instant::now() + budget;
+        self.guess_until(history, |_, _| {}, Some(deadline))
+    }
+
+    fn guess_until(
+        &mut self,
+        history: &[Guess],
+        mut on_progress: impl FnMut(usize, usize),
+        deadline: Option<Instant>,
+    ) -> String {
+        self.narrow(history);
+        if self.remaining.is_empty() {
+            return self.fallback_guess(history);
         }
+        let excluded = self.excluded();
 
-        if history.is_empty() {
-            self.last_guess_idx = Some(
-                self.remaining
-                    .iter()
-                    .find(|(word, _, _)| &**word == "trace")
-                    .map(|&(_, _, idx)| idx)
-                    .unwrap(),
-            );
-            return "trace".to_string();
+        // A custom dictionary may not have the opener, in which case the first turn is scored
+        // like any other.
+        let opener = match self.options.opener {
+            Some(opener) if history.is_empty() && letter_bits(opener) & excluded == 0 => {
+                self.all.iter().find(|(word, _, _)| **word == *opener)
+            }
+            _ => None,
+        };
+        if let Some(&(word, _, idx)) = opener {
+            self.last_guess_idx = Some(idx);
+            return word.to_string();
         } else if self.options.rank_by == Rank::First || self.remaining.len() == 1 {
             let w = self.remaining.first().unwrap();
             self.last_guess_idx = Some(w.2);
             return w.0.to_string();
         }
 
+        if let (Some(openings), [first]) = (&self.options.openings, history) {
+            let planned = self.options.opener == Some(&*first.word);
+            let known = openings
+                .get(&first.mask)
+                .filter(|_| planned)
+                .and_then(|word| self.all.iter().find(|(w, _, _)| **w == **word));
+            if let Some(&(word, _, idx)) = known {
+                self.last_guess_idx = Some(idx);
+                return word.to_string();
+            }
+        }
+
+        if let Some(threshold) = self.options.endgame {
+            if self.remaining.len() <= threshold.min(MAX_EXACT) {
+                return self.endgame_guess(history);
+            }
+        }
+
+        let (turn, scan, total) = self.plan(history);
+
+        #[cfg(feature = "parallel")]
+        let best = if self.options.parallel && deadline.is_none() {
+            use rayon::prelude::*;
+            // The thread-local cache is far too large to build once per thread, so unless there
+            // is a shared `ComputeCache`, the parallel scan computes every mask afresh.
+            scan.par_iter()
+                .map(|&(_, &(word, count, word_idx))| Candidate {
+                    word,
+                    goodness: self.goodness(&word, count, word_idx, &turn, false),
+                    weight: count,
+                    count: self.count(word_idx),
+                    idx: word_idx,
+                })
+                .max_by(Candidate::cmp)
+        } else {
+            self.best_sequential(&scan, total, &turn, &mut on_progress, deadline)
+        };
+        #[cfg(not(feature = "parallel"))]
+        let best = self.best_sequential(&scan, total, &turn, &mut on_progress, deadline);
+        on_progress(total, total);
+
+        let best = best.unwrap();
+        // Out of time, the only word scored may well tell nothing.
+        assert!(deadline.is_some() || best.goodness != 0.0);
+        let best = match self.options.aesthetic {
+            Some((epsilon, scorer)) if deadline.is_none() => {
+                self.prettiest(&scan, &turn, best, epsilon, scorer)
+            }
+            _ => best,
+        };
+        self.entropy.push(turn.remaining_entropy);
+        self.last_guess_idx = Some(best.idx);
+        best.word.to_string()
+    }
+}
+
+impl Solver {
+    /// Apply `history` to the candidates, giving up on [`Options::excluded_letters`] if that
+    /// leaves none.
+    fn narrow(&mut self, history: &[Guess]) {
+        self.catch_up(history);
+        if self.remaining.is_empty() && self.excluded() != 0 {
+            // Start over without the exclusions.
+            self.used_excluded = true;
+            self.remaining = self.starting_candidates();
+            self.seen = 0;
+            self.absent = 0;
+            if let Some(rejected) = &mut self.rejected {
+                rejected.clear();
+            }
+            self.catch_up(history);
+        }
+    }
+
+    /// [`Guesser::guess_multi`]: the word whose goodness, summed over the boards, is highest.
+    ///
+    /// The boards shown change as they are solved, so rather than keep its own state between
+    /// turns, this gives each board a [`Solver::fresh`] solver caught up on its history, and
+    /// scores every word against each of them. With the default [`Rank::ExpectedScore`], that
+    /// picks the word expected to leave the fewest guesses to play across all the boards.
+    fn guess_boards(&mut self, boards: &[Vec<Guess>]) -> String {
+        if boards.len() == 1 || boards[0].is_empty() {
+            // Alone, or before the first clue, every board is the same.
+            return self.guess(&boards[0]);
+        }
+        let solvers: Vec<(Solver, &[Guess])> = boards
+            .iter()
+            .map(|board| {
+                let mut solver = self.fresh();
+                solver.narrow(board);
+                (solver, &board[..])
+            })
+            .filter(|(solver, _)| !solver.remaining.is_empty())
+            .collect();
+        let Some((first, _)) = solvers.first() else {
+            return self.fallback_guess(&boards[0]);
+        };
+        if self.options.rank_by == Rank::First {
+            return first.remaining[0].0.to_string();
+        }
+        let turns: Vec<Turn> = solvers
+            .iter()
+            .map(|(solver, history)| solver.plan(history).0)
+            .collect();
+
+        // The boards all share the same guesses, so any of them tells what has been played.
+        let history = &boards[0];
+        let excluded = self.excluded();
+        let best = self
+            .all
+            .iter()
+            .filter(|(word, _, _)| !history.iter().any(|g| g.word == **word))
+            .filter(|(word, _, _)| letter_bits(word) & excluded == 0)
+            .map(|&(word, count, word_idx)| Candidate {
+                word,
+                goodness: solvers
+                    .iter()
+                    .zip(&turns)
+                    .map(|((solver, _), turn)| solver.goodness(&word, count, word_idx, turn, false))
+                    .sum(),
+                weight: count,
+                count: self.count(word_idx),
+                idx: word_idx,
+            })
+            .max_by(Candidate::cmp);
+        match best {
+            Some(best) => best.word.to_string(),
+            None => self.fallback_guess(history),
+        }
+    }
+
+    /// What to play when nothing fits the history, see [`Options::fallback`].
+    fn fallback_guess(&mut self, history: &[Guess]) -> String {
+        let played = |word: &str| history.iter().any(|g| g.word == word);
+        let &(word, _, idx) = self
+            .options
+            .fallback
+            .and_then(|fallback| self.all.iter().find(|(w, _, _)| **w == *fallback))
+            .filter(|(w, _, _)| !played(w))
+            .or_else(|| self.all.iter().find(|(w, _, _)| !played(w)))
+            .unwrap_or(&self.all[0]);
+        self.last_guess_idx = Some(idx);
+        word.to_string()
+    }
+
+    /// Every word this turn's guess would be chosen from, best first, with the score it is
+    /// ranked by (higher is better). The first word is the one [`Guesser::guess`] would play,
+    /// except on the opening turn if [`Options::opener`] is set.
+    ///
+    /// This works on a copy of the solver, so it doesn't affect later guesses. With
+    /// [`Rank::First`] nothing is scored, and every candidate gets `0.0` in candidate order.
+    pub fn rank(&self, history: &[Guess]) -> Vec<(&str, f64)> {
+        let mut solver = self.clone();
+        solver.catch_up(history);
+        // The words are looked up in `self`, which has the same dictionary as the copy.
+        let word = |idx: usize| &*self.all[idx].0;
+        if solver.options.rank_by == Rank::First {
+            return solver
+                .remaining
+                .iter()
+                .map(|&(_, _, idx)| (word(idx), 0.0))
+                .collect();
+        }
+
+        let (turn, scan, _) = solver.plan(history);
+        let mut ranked: Vec<_> = scan
+            .into_iter()
+            .map(|(_, &(word, count, word_idx))| Candidate {
+                word,
+                goodness: solver.goodness(&word, count, word_idx, &turn, solver.options.cache),
+                weight: count,
+                count: solver.count(word_idx),
+                idx: word_idx,
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.cmp(a));
+        ranked
+            .into_iter()
+            .map(|c| (word(c.idx), c.goodness))
+            .collect()
+    }
+
+    /// The guess [`Options::endgame`] plays.
+    fn endgame_guess(&mut self, history: &[Guess]) -> String {
+        let remaining: Vec<&str> = self.remaining.iter().map(|(w, _, _)| &**w).collect();
+        let allowed: Vec<&str> = if self.options.hard_mode {
+            Vec::new()
+        } else {
+            self.all
+                .iter()
+                .map(|(w, _, _)| &**w)
+                .filter(|&w| !history.iter().any(|g| g.word == w))
+                .filter(|&w| letter_bits(w) & self.excluded() == 0)
+                .collect()
+        };
+        let (word, _) = optimal_guess(&remaining, &allowed);
+        let &(_, _, idx) = self.all.iter().find(|(w, _, _)| **w == *word).unwrap();
+        self.last_guess_idx = Some(idx);
+        word.to_string()
+    }
+
+    /// How many guesses, counting this one, it is expected to take to solve the game by guessing
+    /// `word` after `history`, looking two guesses ahead.
+    ///
+    /// The first ply is `word` itself. Each pattern it could give leaves some candidates, and
+    /// for each of those sets the best second guess is found by trying every candidate in it
+    /// (only candidates, to keep this affordable). The sets left after the second guess are not
+    /// searched further: one word takes one more guess, and anything larger is estimated from
+    /// its entropy with the same regression [`Rank::ExpectedScore`] uses. Candidates count as
+    /// likely as their weight.
+    ///
+    /// Unlike the entropy of a single guess, this sees when a guess leaves sets that no
+    /// follow-up can split well, so it can prefer a word that is worse on its own.
+    pub fn expected_guesses(&self, word: &str, history: &[Guess]) -> f64 {
+        let mut solver = self.clone();
+        solver.catch_up(history);
+        let candidates: Vec<(&str, f64)> = solver
+            .remaining
+            .iter()
+            .map(|(w, p, _)| (&**w, *p))
+            .collect();
+        1.0 + guesses_after(word, &candidates, 1)
+    }
+
+    /// The word whose worst-case mask leaves the fewest candidates after `history`, and the
+    /// share of candidates left in that worst case. Every word in the dictionary is tried, so
+    /// the best split is often a probe that can't be the answer, even in hard mode. Between
+    /// equally even splits, a word that could be the answer wins, then the more common word.
+    /// Every candidate counts once, whatever its weight.
+    ///
+    /// This is the best probe rather than the best guess: it ignores the chance of winning
+    /// outright, and only looks at the largest group, where [`Guesser::guess`] weighs every
+    /// group by its size. A word that leaves many small groups and one big one scores well
+    /// on entropy but badly here.
+    pub fn best_split(&self, history: &[Guess]) -> (&str, f64) {
+        let mut solver = self.clone();
+        solver.catch_up(history);
+        let candidates: Vec<(&str, usize)> =
+            solver.remaining.iter().map(|(w, _, _)| (&**w, 1)).collect();
+        let possible: HashSet<&str> = candidates.iter().map(|&(w, _)| w).collect();
+        let mut best: Option<(&str, usize, bool)> = None;
+        // `all` is most common first, so keeping the first of equals keeps the most common.
+        for word in self.words() {
+            let worst = partition(word, &candidates).into_iter().max().unwrap_or(0);
+            let candidate = possible.contains(word);
+            if best.is_none_or(|(_, w, c)| worst < w || (worst == w && candidate && !c)) {
+                best = Some((word, worst, candidate));
+            }
+        }
+        let (word, worst, _) = best.expect("dictionary is empty");
+        (word, worst as f64 / candidates.len() as f64)
+    }
+
+    /// The best first guess for this solver's dictionary and options, found by scoring every
+    /// word, even with [`Options::cutoff`] set. [`Options::opener`] normally skips that work,
+    /// and this is a way to check (or pick) it.
+    ///
+    /// The result is cached, so only the first call is slow, and is the same whatever turn the
+    /// solver is on.
+    pub fn best_opener(&self) -> &str {
+        self.best_opener.get_or_init(|| {
+            let mut fresh = self.clone();
+            fresh.reset();
+            // The cutoff would stop after the likeliest words, and this is meant to be exhaustive.
+            fresh.options.cutoff = false;
+            Word::new(fresh.rank(&[])[0].0)
+        })
+    }
+
+    /// The words that can be the answer, before anything is known about it.
+    fn starting_candidates(&self) -> Cow<'static, [Entry]> {
+        let excluded = self.excluded();
+        if excluded != 0 {
+            let mut candidates = self.starting_answers();
+            retain(&mut candidates, |word, _| letter_bits(word) & excluded == 0);
+            return candidates;
+        }
+        self.starting_answers()
+    }
+
+    /// [`Solver::starting_candidates`], ignoring [`Options::excluded_letters`].
+    fn starting_answers(&self) -> Cow<'static, [Entry]> {
+        match &self.options.answers {
+            None => self.all.clone(),
+            Some(answers) => {
+                let answers: HashSet<&str> = answers.iter().map(|word| &**word).collect();
+                self.all
+                    .iter()
+                    .filter(|(word, _, _)| answers.contains(&**word))
+                    .copied()
+                    .collect()
+            }
+        }
+    }
+
+    /// [`Options::excluded_letters`] as one bit per letter, like [`letter_bits`], or none once
+    /// they have been given up on.
+    fn excluded(&self) -> u32 {
+        if self.used_excluded {
+            return 0;
+        }
+        self.options
+            .excluded_letters
+            .iter()
+            .enumerate()
+            .filter(|(_, &excluded)| excluded)
+            .fold(0, |bits, (i, _)| bits | 1 << i)
+    }
+
+    /// Narrow down `remaining` with any history entries not applied yet.
+    fn catch_up(&mut self, history: &[Guess]) {
+        // Normally only the last guess is new, but a wrapper may have answered some turns
+        // without asking us, so catch up on everything we haven't seen.
+        for (by, guess) in history.iter().enumerate().skip(self.seen) {
+            // Dropping words with a letter known to be absent is much cheaper than working out
+            // their mask, and catches most of the words this guess rules out.
+            self.absent |= guess.absent_letters();
+            let absent = self.absent;
+            // The cache row is only known for the guess we made ourselves.
+            let own = self
+                .last_guess_idx
+                .filter(|&idx| by + 1 == history.len() && *self.all[idx].0 == *guess.word);
+            #[cfg(feature = "compute-cache")]
+            if let (Some(idx), Some(table)) = (own, self.options.compute_cache.clone()) {
+                let reference = PackedCorrectness::from(guess.mask);
+                self.trim(by, |word, word_idx| {
+                    letter_bits(word) & absent == 0 && reference == table.get(idx, word_idx)
+                });
+                continue;
+            }
+            match own {
+                Some(idx) if self.options.cache => {
+                    let reference = PackedCorrectness::from(guess.mask);
+                    COMPUTES.with(|c| {
+                        let row = &c.get().unwrap()[idx];
+                        self.trim(by, |word, word_idx| {
+                            letter_bits(word) & absent == 0
+                                && reference == get_packed(row, &guess.word, word, word_idx)
+                        });
+                    });
+                }
+                _ => {
+                    let constraints = Constraints::from_history(std::slice::from_ref(guess));
+                    self.trim(by, |word, _| {
+                        letter_bits(word) & absent == 0 && constraints.permits(word)
+                    });
+                }
+            }
+        }
+        self.seen = history.len();
+    }
+
+    /// What this turn's candidates are measured against, the words to score in scan order, and
+    /// how many words the scan is out of, for progress reports.
+    fn plan(&self, history: &[Guess]) -> (Turn, Vec<(usize, &Entry)>, usize) {
         assert!(!self.remaining.is_empty());
 
         let remaining_p: f64 = self.remaining.iter().map(|&(_, p, _)| p).sum();
@@ -248,96 +1174,359 @@ impl Guesser for Solver {
                 p * p.log2()
             })
             .sum::<f64>();
-        self.entropy.push(remaining_entropy);
 
-        let mut best: Option<Candidate> = None;
-        let mut i = 0;
-        let stop = (self.remaining.len() / 3).max(20).min(self.remaining.len());
-        let consider = if self.options.hard_mode {
+        let explore = self.options.hard_mode
+            && self.options.exploration.is_some_and(|slack| {
+                (3..=EXPLORE_MAX).contains(&self.remaining.len())
+                    && BUDGET.saturating_sub(history.len()) >= slack + 2
+            });
+        let scanning_remaining = self.options.hard_mode && !explore;
+        let consider = if scanning_remaining {
             &*self.remaining
         } else {
-            INITIAL_SIGMOID.get().unwrap()
+            &*self.all
         };
+        let turn = Turn {
+            score: history.len() as f64,
+            remaining_p,
+            remaining_entropy,
+            rank_by: if explore {
+                Rank::ExpectedInformation
+            } else {
+                self.options.rank_by
+            },
+        };
+        let mut scan = self.scan_order(consider, scanning_remaining, explore);
+        // A word already played can only be a candidate if it was the answer, so playing it
+        // again would waste a turn. The cutoff only counts candidates, so this doesn't move it.
+        scan.retain(|(_, (word, _, _))| !history.iter().any(|g| g.word == **word));
+        if explore {
+            // Still hard mode: the probe has to fit every clue so far.
+            scan.retain(|(_, (word, _, _))| history.iter().all(|g| g.matches(word)));
+        }
+        let excluded = self.excluded();
+        scan.retain(|(_, (word, _, _))| letter_bits(word) & excluded == 0);
+        (turn, scan, consider.len())
+    }
 
-        for &(word, count, word_idx) in consider {
-            // Rather than iterate over the patterns sequentially and add up the counts of words
-            // that result in that pattern, Popoki can instead keep a running total for each pattern
-            // simultaneously by storing them in an array. It can do this since each candidate-word
-            // pair deterministically produces only one mask.
-
-            let mut totals = [0.0f64; MAX_MASK_ENUM];
-
-            let mut in_remaining = false;
-            if self.options.cache {
-                COMPUTES.with(|c| {
-                    let row = &c.get().unwrap()[word_idx];
-                    for (candidate, count, candidate_idx) in &*self.remaining {
-                        in_remaining |= word_idx == *candidate_idx;
-                        let idx = get_packed(row, word, candidate, *candidate_idx);
-                        totals[usize::from(u8::from(idx))] += count;
+    /// The words to score this turn, each with its position in `consider`, in the order the
+    /// sequential scan visits them. This applies anagram deduplication and the cutoff, which
+    /// only depend on the order of `consider` and not on any scores.
+    fn scan_order<'c>(
+        &self,
+        consider: &'c [Entry],
+        scanning_remaining: bool,
+        explore: bool,
+    ) -> Vec<(usize, &'c Entry)> {
+        let counting = self.options.cutoff && !explore;
+        let remaining: HashSet<usize> = if counting && !scanning_remaining {
+            self.remaining.iter().map(|&(_, _, idx)| idx).collect()
+        } else {
+            HashSet::new()
+        };
+
+        // Letters no candidate has are gray wherever they are, so anagrams that only move those
+        // around get the same masks, and so the same score.
+        let present = self
+            .remaining
+            .iter()
+            .fold(0, |bits, (word, _, _)| bits | letter_bits(word));
+        let mut anagrams: HashMap<([u8; 5], [u8; 5]), usize> = HashMap::new();
+        let mut scan: Vec<(usize, &Entry)> = Vec::new();
+        let mut i = 0;
+        let stop = (self.remaining.len() / 3).max(20).min(self.remaining.len());
+        for (pos, entry) in consider.iter().enumerate() {
+            let (word, _, word_idx) = *entry;
+            if self.options.dedup_anagrams {
+                let mut letters = word.0;
+                letters.sort_unstable();
+                let placed = word.0.map(|l| {
+                    if present & 1 << (l - b'a') != 0 {
+                        l
+                    } else {
+                        b'.'
                     }
                 });
-            } else {
-                for (candidate, count, candidate_idx) in &*self.remaining {
-                    in_remaining |= word_idx == *candidate_idx;
-                    let idx = PackedCorrectness::from(Correctness::compute(candidate, word));
-                    totals[usize::from(u8::from(idx))] += count;
+                if let Some(&kept) = anagrams.get(&(letters, placed)) {
+                    // Only a word outside the candidates can share its key, so this doesn't
+                    // move the cutoff. Keep whichever would win the tie.
+                    let kept = &mut scan[kept].1;
+                    if self.breaks_tie(entry, kept) {
+                        *kept = entry;
+                    }
+                    continue;
                 }
+                anagrams.insert((letters, placed), scan.len());
             }
 
-            let sum: f64 = totals
-                .into_iter()
-                .filter(|t| *t != 0.0)
-                .map(|p| {
-                    let p_of_this_pattern = p as f64 / remaining_p as f64;
-                    p_of_this_pattern * p_of_this_pattern.log2()
-                })
-                .sum();
+            scan.push((pos, entry));
 
-            let p_word = if in_remaining {
-                count as f64 / remaining_p as f64
-            } else {
-                0.0
-            };
-            let e_info = -sum;
-            let goodness = match self.options.rank_by {
-                Rank::First => unreachable!("early return above"),
-                Rank::ExpectedScore => {
-                    // NOTE: Higher is better, so we negate the result
-                    -(p_word * (score + 1.0)
-                        + (1.0 - p_word) * (score + est_steps_left(remaining_entropy - e_info)))
+            if counting && (scanning_remaining || remaining.contains(&word_idx)) {
+                i += 1;
+                if i >= stop {
+                    break;
                 }
-                Rank::WeightedInformation => p_word * e_info,
-                Rank::InfoPlusProbability => p_word + e_info,
-                Rank::ExpectedInformation => e_info,
+            }
+        }
+        scan
+    }
+
+    /// Whether `a` beats `b` when both score the same, as [`Candidate::cmp`] decides.
+    fn breaks_tie(&self, a: &Entry, b: &Entry) -> bool {
+        let (a_word, a_weight, a_idx) = *a;
+        let (b_word, b_weight, b_idx) = *b;
+        a_weight
+            .total_cmp(&b_weight)
+            .then(self.count(a_idx).cmp(&self.count(b_idx)))
+            .then(b_word.cmp(&a_word))
+            == Ordering::Greater
+    }
+
+    fn best_sequential(
+        &self,
+        scan: &[(usize, &Entry)],
+        total: usize,
+        turn: &Turn,
+        on_progress: &mut impl FnMut(usize, usize),
+        deadline: Option<Instant>,
+    ) -> Option<Candidate> {
+        let mut best: Option<Candidate> = None;
+        let mut next_report = PROGRESS_EVERY;
+        for &(pos, &(word, count, word_idx)) in scan {
+            while pos >= next_report {
+                on_progress(next_report, total);
+                next_report += PROGRESS_EVERY;
+            }
+
+            let candidate = Candidate {
+                word,
+                goodness: self.goodness(&word, count, word_idx, turn, self.options.cache),
+                weight: count,
+                count: self.count(word_idx),
+                idx: word_idx,
             };
-            if let Some(c) = best {
-                if goodness > c.goodness {
-                    best = Some(Candidate {
-                        word,
-                        goodness,
-                        idx: word_idx,
-                    });
-                }
-            } else {
-                best = Some(Candidate {
-                    word,
-                    goodness,
-                    idx: word_idx,
-                });
+            if best.is_none_or(|c| candidate.cmp(&c) == Ordering::Greater) {
+                best = Some(candidate);
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
             }
+        }
+        best
+    }
 
-            if self.options.cutoff && in_remaining {
-                i += 1;
-                if i >= stop {
-                    break;
+    /// Of the words in `scan` that score within `epsilon` of `best`, the one whose mask
+    /// `scorer` likes most on average over the candidates, see [`Options::aesthetic`].
+    fn prettiest(
+        &self,
+        scan: &[(usize, &Entry)],
+        turn: &Turn,
+        best: Candidate,
+        epsilon: f64,
+        scorer: MaskScorer,
+    ) -> Candidate {
+        let mut prettiest = (f64::NEG_INFINITY, best);
+        for &(_, &(word, count, word_idx)) in scan {
+            let goodness = self.goodness(&word, count, word_idx, turn, self.options.cache);
+            if goodness < best.goodness - epsilon {
+                continue;
+            }
+            let beauty = self
+                .remaining
+                .iter()
+                .map(|(answer, p, _)| p * f64::from(scorer(&Correctness::compute(answer, &word))))
+                .sum::<f64>()
+                / turn.remaining_p;
+            let candidate = Candidate {
+                word,
+                goodness,
+                weight: count,
+                count: self.count(word_idx),
+                idx: word_idx,
+            };
+            let prettier = beauty
+                .total_cmp(&prettiest.0)
+                .then(candidate.cmp(&prettiest.1));
+            if prettier == Ordering::Greater {
+                prettiest = (beauty, candidate);
+            }
+        }
+        prettiest.1
+    }
+
+    fn goodness(&self, word: &str, count: f64, word_idx: usize, turn: &Turn, cache: bool) -> f64 {
+        // Rather than iterate over the patterns sequentially and add up the counts of words
+        // that result in that pattern, Popoki can instead keep a running total for each pattern
+        // simultaneously by storing them in an array. It can do this since each candidate-word
+        // pair deterministically produces only one mask.
+
+        let mut totals = [0.0f64; MAX_MASK_ENUM];
+        // Which patterns got any candidates, so the sum below can skip the rest. Late in a game
+        // only a handful of the 243 are hit.
+        let mut occupied = [0u64; MAX_MASK_ENUM.div_ceil(64)];
+
+        let mut in_remaining = false;
+        let mut tally = |mask: PackedCorrectness, count: f64, candidate_idx: usize| {
+            in_remaining |= word_idx == candidate_idx;
+            let idx = usize::from(u8::from(mask));
+            totals[idx] += count;
+            occupied[idx / 64] |= 1 << (idx % 64);
+        };
+        if let Some(lookup) = self.precomputed(word_idx) {
+            for &(_, count, candidate_idx) in &*self.remaining {
+                tally(lookup(candidate_idx), count, candidate_idx);
+            }
+        } else if cache {
+            COMPUTES.with(|c| {
+                let row = &c.get().unwrap()[word_idx];
+                for (candidate, count, candidate_idx) in &*self.remaining {
+                    let mask = get_packed(row, word, candidate, *candidate_idx);
+                    tally(mask, *count, *candidate_idx);
                 }
+            });
+        } else {
+            for (candidate, count, candidate_idx) in &*self.remaining {
+                tally(
+                    Correctness::compute_packed(candidate, word),
+                    *count,
+                    *candidate_idx,
+                );
             }
         }
-        let best = best.unwrap();
-        assert_ne!(best.goodness, 0.0);
-        self.last_guess_idx = Some(best.idx);
-        best.word.to_string()
+
+        // Set bits are visited lowest first, so the terms are added in the same order as a walk
+        // over every pattern would.
+        let sum: f64 = occupied
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, mut bits)| {
+                core::iter::from_fn(move || {
+                    let bit = bits.trailing_zeros();
+                    bits &= bits.checked_sub(1)?;
+                    Some(totals[i * 64 + bit as usize])
+                })
+            })
+            .filter(|t| *t != 0.0)
+            .map(|p| {
+                let p_of_this_pattern = p / turn.remaining_p;
+                p_of_this_pattern * p_of_this_pattern.log2()
+            })
+            .sum();
+
+        let p_word = if in_remaining {
+            count / turn.remaining_p
+        } else {
+            0.0
+        };
+        let e_info = -sum;
+        let score = turn.score;
+        let goodness = match turn.rank_by {
+            Rank::First => unreachable!("early return above"),
+            Rank::ExpectedScore => {
+                // NOTE: Higher is better, so we negate the result
+                -(p_word * (score + 1.0)
+                    + (1.0 - p_word) * (score + est_steps_left(turn.remaining_entropy - e_info)))
+            }
+            Rank::WeightedInformation => p_word * e_info,
+            Rank::InfoPlusProbability => p_word + e_info,
+            Rank::ExpectedInformation => e_info,
+        };
+        goodness + self.options.solve_bonus * p_word
+    }
+}
+
+impl Solver {
+    /// Looks up the mask of `dictionary[guess_idx]` against a word by its index in
+    /// [`Options::compute_cache`], if there is one.
+    #[cfg(feature = "compute-cache")]
+    fn precomputed(&self, guess_idx: usize) -> Option<impl Fn(usize) -> PackedCorrectness + '_> {
+        let table = self.options.compute_cache.as_deref()?;
+        Some(move |answer_idx| table.get(guess_idx, answer_idx))
+    }
+
+    #[cfg(not(feature = "compute-cache"))]
+    fn precomputed(&self, _guess_idx: usize) -> Option<fn(usize) -> PackedCorrectness> {
+        None
+    }
+}
+
+/// The expected number of guesses still needed after guessing `word` against `candidates`,
+/// searching `plies` more guesses ahead before estimating.
+fn guesses_after(word: &str, candidates: &[(&str, f64)], plies: usize) -> f64 {
+    let mut groups = vec![Vec::new(); MAX_MASK_ENUM];
+    for &(candidate, p) in candidates {
+        let mask = Correctness::compute_packed(candidate, word);
+        groups[usize::from(u8::from(mask))].push((candidate, p));
+    }
+    let total: f64 = candidates.iter().map(|&(_, p)| p).sum();
+
+    let mut expected = 0.0;
+    for group in groups.iter().filter(|g| !g.is_empty()) {
+        let p: f64 = group.iter().map(|&(_, p)| p).sum();
+        let more = if group.len() == 1 {
+            // Either `word` was the answer, or the answer is now known.
+            if group[0].0 == word {
+                0.0
+            } else {
+                1.0
+            }
+        } else if plies == 0 {
+            let entropy: f64 = group
+                .iter()
+                .map(|&(_, q)| {
+                    let q = q / p;
+                    -q * q.log2()
+                })
+                .sum();
+            est_steps_left(entropy)
+        } else {
+            group
+                .iter()
+                .map(|&(next, _)| 1.0 + guesses_after(next, group, plies - 1))
+                .fold(f64::INFINITY, f64::min)
+        };
+        expected += p / total * more;
+    }
+    expected
+}
+
+impl Guesser for Solver {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        self.guess_with_progress(history, |_, _| {})
+    }
+
+    fn guess_within(&mut self, history: &[Guess], budget: Duration) -> String {
+        Solver::guess_within(self, history, budget)
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.remaining.len())
+    }
+
+    fn guess_multi(&mut self, boards: &[Vec<Guess>]) -> String {
+        self.guess_boards(boards)
+    }
+
+    fn reset(&mut self) {
+        // Only the remaining set and what was learned during the game need to go. The weighted
+        // word list is shared with the remaining set, so this doesn't reweigh, and only has to
+        // refilter if there is a separate answer list or excluded letters.
+        self.used_excluded = false;
+        self.remaining = self.starting_candidates();
+        self.entropy.clear();
+        self.last_guess_idx = None;
+        self.seen = 0;
+        self.absent = 0;
+        if let Some(rejected) = &mut self.rejected {
+            rejected.clear();
+        }
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        // Hints aren't history entries, so there's nothing to log them against.
+        retain(&mut self.remaining, |word, _| {
+            word.as_bytes()[position] == letter
+        });
     }
 
     fn finish(&self, guesses: usize) {
@@ -349,3 +1538,58 @@ impl Guesser for Solver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn best(a: Candidate, b: Candidate) -> String {
+        assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        [a, b]
+            .into_iter()
+            .max_by(Candidate::cmp)
+            .unwrap()
+            .word
+            .to_string()
+    }
+
+    fn candidate(word: &str, weight: f64) -> Candidate {
+        let idx = DICTIONARY.iter().position(|&(w, _)| w == word).unwrap();
+        Candidate {
+            word: Word::new(word),
+            goodness: -2.5,
+            weight,
+            count: DICTIONARY[idx].1,
+            idx,
+        }
+    }
+
+    #[test]
+    fn ties_break_deterministically() {
+        // The better-weighted word wins.
+        let (a, b) = (candidate("tract", 0.5), candidate("track", 0.9));
+        assert_eq!(best(a, b), "track");
+        assert_eq!(best(b, a), "track");
+
+        // Then the more common one, which `track` is.
+        let (a, b) = (candidate("tract", 1.0), candidate("track", 1.0));
+        assert_eq!(best(a, b), "track");
+        assert_eq!(best(b, a), "track");
+
+        // Then the alphabetically first.
+        let (a, b) = (candidate("track", 1.0), candidate("track", 1.0));
+        let b = Candidate {
+            word: Word::new("aaaaa"),
+            ..b
+        };
+        assert_eq!(best(a, b), "aaaaa");
+        assert_eq!(best(b, a), "aaaaa");
+
+        // None of which matters if one is simply better.
+        let better = Candidate {
+            goodness: -2.0,
+            ..candidate("zonal", 0.1)
+        };
+        assert_eq!(best(candidate("track", 1.0), better), "zonal");
+    }
+}
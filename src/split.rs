@@ -0,0 +1,227 @@
+use crate::{Correctness, MAX_MASK_ENUM};
+use std::collections::HashMap;
+
+/// Bucket `candidates` by the pattern `guess` would produce if each of them were the answer.
+///
+/// The returned array is indexed by packed pattern, and each bucket holds the summed weight of
+/// the candidates that land in it.
+pub fn partition(guess: &str, candidates: &[(&str, usize)]) -> [usize; MAX_MASK_ENUM] {
+    let mut buckets = [0; MAX_MASK_ENUM];
+    for &(candidate, count) in candidates {
+        let idx = Correctness::compute_packed(candidate, guess);
+        buckets[usize::from(u8::from(idx))] += count;
+    }
+    buckets
+}
+
+/// A way of scoring how well a guess splits the candidate set. Higher is better.
+pub trait SplitCriterion {
+    fn score(&self, buckets: &[usize; MAX_MASK_ENUM]) -> f64;
+}
+
+/// E[information], in bits.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Entropy;
+
+impl SplitCriterion for Entropy {
+    fn score(&self, buckets: &[usize; MAX_MASK_ENUM]) -> f64 {
+        let total: usize = buckets.iter().sum();
+        -buckets
+            .iter()
+            .filter(|&&b| b != 0)
+            .map(|&b| {
+                let p = b as f64 / total as f64;
+                p * p.log2()
+            })
+            .sum::<f64>()
+    }
+}
+
+/// Negated weight of the largest bucket, i.e. the worst case (Knuth-style minimax).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WorstCase;
+
+impl SplitCriterion for WorstCase {
+    fn score(&self, buckets: &[usize; MAX_MASK_ENUM]) -> f64 {
+        -(buckets.iter().copied().max().unwrap_or(0) as f64)
+    }
+}
+
+/// Negated expected weight of the bucket the answer ends up in.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExpectedSize;
+
+impl SplitCriterion for ExpectedSize {
+    fn score(&self, buckets: &[usize; MAX_MASK_ENUM]) -> f64 {
+        let total: usize = buckets.iter().sum();
+        -buckets
+            .iter()
+            .map(|&b| b as f64 * b as f64 / total as f64)
+            .sum::<f64>()
+    }
+}
+
+/// Find the best first guess in `dict` under `criterion`, along with its score.
+///
+/// Every word is tried as a guess against every other word, so this is an O(n²·243) sweep; it
+/// takes a while on the bundled dictionary. Nothing is cached. Ties go to the earlier word.
+///
+/// # Panics
+///
+/// Panics if `dict` is empty.
+pub fn best_opener_by<'d, C: SplitCriterion>(
+    dict: &[(&'d str, usize)],
+    criterion: &C,
+) -> (&'d str, f64) {
+    let mut best: Option<(&str, f64)> = None;
+    for &(word, _) in dict {
+        let score = criterion.score(&partition(word, dict));
+        if best.is_none_or(|(_, s)| score > s) {
+            best = Some((word, score));
+        }
+    }
+    best.expect("dictionary is empty")
+}
+
+/// For every pattern `opener` can produce over `dict`, find the best second guess under
+/// `criterion` among the words that pattern leaves.
+///
+/// The table is keyed by packed pattern (see [`PackedCorrectness`](crate::PackedCorrectness));
+/// patterns no word in `dict` produces are left out. A pattern that leaves a single word maps
+/// to that word.
+pub fn second_guess_table<'d, C: SplitCriterion>(
+    opener: &str,
+    dict: &[(&'d str, usize)],
+    criterion: &C,
+) -> HashMap<u8, &'d str> {
+    let mut buckets: HashMap<u8, Vec<(&str, usize)>> = HashMap::new();
+    for &(word, count) in dict {
+        let pattern = Correctness::compute_packed(word, opener);
+        buckets
+            .entry(u8::from(pattern))
+            .or_default()
+            .push((word, count));
+    }
+
+    buckets
+        .into_iter()
+        .map(|(pattern, bucket)| {
+            let best = match *bucket {
+                [(word, _)] => word,
+                _ => best_opener_by(&bucket, criterion).0,
+            };
+            (pattern, best)
+        })
+        .collect()
+}
+
+/// Find the word that, played blind as the second guess after `first`, gives the most combined
+/// information, along with that joint entropy in bits.
+///
+/// The second word is picked without knowing the feedback `first` got, which is what you want
+/// for a memorized pair of openers. Like [`best_opener_by`] this is an O(n²) sweep, and ties go
+/// to the earlier word.
+///
+/// Words with a count of zero are tried as the second guess, but can't be the answer. If every
+/// count is zero, no pair tells anything and the first word is returned with `0.0`.
+///
+/// # Panics
+///
+/// Panics if `dict` is empty.
+pub fn best_complementary_opener<'d>(first: &str, dict: &[(&'d str, usize)]) -> (&'d str, f64) {
+    let firsts: Vec<usize> = dict
+        .iter()
+        .map(|&(word, _)| usize::from(u8::from(Correctness::compute_packed(word, first))))
+        .collect();
+    let total: usize = dict.iter().map(|&(_, count)| count).sum();
+
+    // One bucket per (first pattern, second pattern) pair; only the touched ones get reset.
+    let mut joint = vec![0usize; MAX_MASK_ENUM * MAX_MASK_ENUM];
+    let mut touched = Vec::new();
+
+    let mut best: Option<(&str, f64)> = None;
+    for &(second, _) in dict {
+        for (&(word, count), &p1) in dict.iter().zip(&firsts) {
+            if count == 0 {
+                continue;
+            }
+            let p2 = Correctness::compute_packed(word, second);
+            let idx = p1 * MAX_MASK_ENUM + usize::from(u8::from(p2));
+            if joint[idx] == 0 {
+                touched.push(idx);
+            }
+            joint[idx] += count;
+        }
+
+        let mut entropy = 0.0;
+        for idx in touched.drain(..) {
+            let p = joint[idx] as f64 / total as f64;
+            entropy -= p * p.log2();
+            joint[idx] = 0;
+        }
+
+        if best.is_none_or(|(_, e)| entropy > e) {
+            best = Some((second, entropy));
+        }
+    }
+    best.expect("dictionary is empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PackedCorrectness;
+
+    const DICT: [(&str, usize); 4] = [("aaaaa", 1), ("bbbbb", 1), ("ccccc", 1), ("abcxx", 1)];
+
+    #[test]
+    fn partition_sums_counts() {
+        let buckets = partition("aaaaa", &[("bbbbb", 2), ("ccccc", 3), ("aaaaa", 1)]);
+        assert_eq!(buckets.iter().sum::<usize>(), 6);
+        assert_eq!(buckets.iter().filter(|&&b| b != 0).count(), 2);
+        assert_eq!(buckets.iter().max(), Some(&5));
+    }
+
+    #[test]
+    fn opener_by_entropy() {
+        assert_eq!(best_opener_by(&DICT, &Entropy), ("abcxx", 2.0));
+    }
+
+    #[test]
+    fn opener_by_minimax() {
+        assert_eq!(best_opener_by(&DICT, &WorstCase), ("abcxx", -1.0));
+    }
+
+    #[test]
+    fn second_guesses() {
+        let table = second_guess_table("aaaaa", &DICT, &Entropy);
+        let key = |mask| u8::from(PackedCorrectness::from(mask));
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[&key(mask![C C C C C])], "aaaaa");
+        assert_eq!(table[&key(mask![C W W W W])], "abcxx");
+        assert_eq!(table[&key(mask![W W W W W])], "bbbbb");
+    }
+
+    #[test]
+    fn complementary_opener() {
+        // `aaaaa` can't tell `bbbbb` and `ccccc` apart, so the best partner is one that can.
+        assert_eq!(best_complementary_opener("aaaaa", &DICT), ("bbbbb", 2.0));
+    }
+
+    #[test]
+    fn complementary_opener_without_counts() {
+        let none: Vec<_> = DICT.iter().map(|&(word, _)| (word, 0)).collect();
+        assert_eq!(best_complementary_opener("aaaaa", &none), ("aaaaa", 0.0));
+
+        // A word that can't be the answer only counts as a guess.
+        let mut more = DICT.to_vec();
+        more.push(("abcab", 0));
+        assert_eq!(best_complementary_opener("aaaaa", &more), ("bbbbb", 2.0));
+    }
+
+    #[test]
+    fn opener_by_expected_size() {
+        assert_eq!(best_opener_by(&DICT, &ExpectedSize), ("abcxx", -1.0));
+        assert_eq!(ExpectedSize.score(&partition("aaaaa", &DICT)), -1.5);
+    }
+}
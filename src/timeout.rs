@@ -0,0 +1,116 @@
+use crate::{Guess, Guesser};
+use std::time::Duration;
+
+/// Gives another guesser a fixed amount of time for each guess, through
+/// [`Guesser::guess_within`]. Made with
+/// [`GuesserExt::with_timeout`](crate::GuesserExt::with_timeout).
+///
+/// Guessers that can't stop early take as long as they take; for [`Solver`](crate::Solver)
+/// this means the best guess it found in time. The budget covers a single guess, not the game.
+#[derive(Debug, Clone)]
+pub struct TimeoutGuesser<G> {
+    inner: G,
+    budget: Duration,
+}
+
+impl<G: Guesser> TimeoutGuesser<G> {
+    /// Play `inner`, giving it `budget` for each guess.
+    pub fn new(inner: G, budget: Duration) -> Self {
+        Self { inner, budget }
+    }
+
+    /// The guesser being given the budget.
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+
+    /// How long each guess may take.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+}
+
+impl<G: Guesser> Guesser for TimeoutGuesser<G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        self.inner.guess_within(history, self.budget)
+    }
+
+    /// Whichever of the two budgets is shorter.
+    fn guess_within(&mut self, history: &[Guess], budget: Duration) -> String {
+        self.inner.guess_within(history, budget.min(self.budget))
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.inner.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.inner.reveal(position, letter);
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.inner.remaining()
+    }
+
+    /// Passed on as is, since there is no way to give a multi-board guess a budget.
+    fn guess_multi(&mut self, boards: &[Vec<Guess>]) -> String {
+        self.inner.guess_multi(boards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GuesserExt, Solver, Wordle};
+
+    #[test]
+    fn plays_the_budgeted_guesses() {
+        let w = Wordle::new();
+        let g = Solver::builder().with_opener("trace").unwrap().build();
+        let history = w
+            .play_recording("humph", g.fresh().with_timeout(Duration::ZERO))
+            .into_history();
+        assert_eq!(history.last().unwrap().word, "humph");
+
+        // Every guess is what the solver comes up with given no time at all.
+        for turn in 0..history.len() {
+            let mut solver = g.fresh();
+            assert_eq!(
+                history[turn].word,
+                solver.guess_within(&history[..turn], Duration::ZERO)
+            );
+        }
+    }
+
+    #[test]
+    fn plenty_of_time_changes_nothing() {
+        let w = Wordle::new();
+        let timed = w.play_recording(
+            "cigar",
+            Solver::default().with_timeout(Duration::from_secs(3600)),
+        );
+        assert_eq!(timed, w.play_recording("cigar", Solver::default()));
+    }
+
+    #[test]
+    fn forwards_guess_multi() {
+        /// Plays a different word for several boards than for one.
+        struct Boards;
+        impl Guesser for Boards {
+            fn guess(&mut self, _: &[Guess]) -> String {
+                "wrong".to_string()
+            }
+
+            fn guess_multi(&mut self, _: &[Vec<Guess>]) -> String {
+                "right".to_string()
+            }
+        }
+
+        let mut timed = Boards.with_timeout(Duration::ZERO);
+        assert_eq!(timed.guess_multi(&[Vec::new(), Vec::new()]), "right");
+    }
+}
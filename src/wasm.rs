@@ -0,0 +1,111 @@
+//! A string-only API for calling the solver from JavaScript through `wasm-bindgen`.
+//!
+//! Words are plain lowercase strings, and masks are five color letters as accepted by
+//! [`Correctness::from_chars`] (`G`reen, `Y`ellow, and `W` or `B` for gray). Build it with
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! ```
+//!
+//! and run `wasm-bindgen` on the resulting `.wasm` file.
+//!
+//! ```
+//! use popoki::wasm::{compute_mask, next_guess};
+//!
+//! let first = next_guess("[]").unwrap();
+//! let mask = compute_mask("hatch", &first).unwrap();
+//! let history = format!(r#"[{{"word": "{}", "mask": "{}"}}]"#, first, mask);
+//! assert_ne!(next_guess(&history).unwrap(), first);
+//! ```
+
+use crate::{check_word, remaining_candidates, Correctness, Guess, Guesser, Solver, DICTIONARY};
+use serde::Deserialize;
+use std::borrow::Cow;
+use wasm_bindgen::prelude::*;
+
+/// One entry of the history passed to [`next_guess`].
+#[derive(Deserialize)]
+struct Turn {
+    word: String,
+    mask: String,
+}
+
+/// The solver's next guess, given the history so far as a JSON array of
+/// `{"word": "trace", "mask": "WYWWG"}` objects, oldest first.
+///
+/// Every call starts a fresh [`Solver`] without the correctness cache, which is far too large
+/// for a browser.
+#[wasm_bindgen]
+pub fn next_guess(history_json: &str) -> Result<String, JsError> {
+    next_guess_from(history_json).map_err(|e| JsError::new(&e))
+}
+
+/// The mask `guess` gets when the answer is `answer`, as five of `G`, `Y` and `W`.
+#[wasm_bindgen]
+pub fn compute_mask(answer: &str, guess: &str) -> Result<String, JsError> {
+    mask_between(answer, guess).map_err(|e| JsError::new(&e))
+}
+
+fn next_guess_from(history_json: &str) -> Result<String, String> {
+    let turns: Vec<Turn> = serde_json::from_str(history_json).map_err(|e| e.to_string())?;
+    let history = turns
+        .into_iter()
+        .map(|turn| {
+            check_word(&turn.word, 5).map_err(|e| e.to_string())?;
+            let mask = Correctness::from_chars(&turn.mask).map_err(|e| e.to_string())?;
+            Ok(Guess {
+                word: Cow::Owned(turn.word),
+                mask,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    // The solver can't cope with running out of candidates.
+    let words = DICTIONARY.iter().map(|&(word, _)| word);
+    if remaining_candidates(words, &history).is_empty() {
+        return Err("no word in the dictionary fits the history".to_string());
+    }
+
+    let mut options = Solver::builder();
+    options.cache = false;
+    Ok(options.build().guess(&history))
+}
+
+fn mask_between(answer: &str, guess: &str) -> Result<String, String> {
+    check_word(answer, 5).map_err(|e| e.to_string())?;
+    check_word(guess, 5).map_err(|e| e.to_string())?;
+    Ok(Correctness::to_chars(&Correctness::compute(answer, guess)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(compute_mask("crane", "trace").unwrap(), "WGGYG");
+
+        let first = next_guess("[]").unwrap();
+        assert_eq!(first, "trace");
+        let history = format!(
+            r#"[{{"word": "trace", "mask": "{}"}}]"#,
+            compute_mask("hatch", "trace").unwrap()
+        );
+        let second = next_guess(&history).unwrap();
+        let mask = Correctness::compute("hatch", "trace");
+        assert!(Guess {
+            word: Cow::Borrowed("trace"),
+            mask,
+        }
+        .matches(&second));
+    }
+
+    #[test]
+    fn bad_input() {
+        assert!(next_guess_from("{").is_err());
+        assert!(next_guess_from(r#"[{"word": "TRACE", "mask": "WWWWW"}]"#).is_err());
+        assert!(next_guess_from(r#"[{"word": "trace", "mask": "WWWW"}]"#).is_err());
+        assert!(next_guess_from(r#"[{"word": "trace", "mask": "GGGGY"}]"#).is_err());
+        assert!(mask_between("crane", "cranes").is_err());
+    }
+}
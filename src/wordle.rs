@@ -0,0 +1,1152 @@
+use crate::{
+    dictionary, expected_optimal_guesses, partition, splitmix64, Constraints, Correctness,
+    DictionaryError, Entropy, Guess, Lang, SplitCriterion, Summary, TimeoutGuesser, DICTIONARY,
+};
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// Answers that are hard to find in few guesses, for quick regression runs with
+/// [`Wordle::evaluate_hard`]. Most belong to a family of words that differ in a single letter,
+/// like `-ight`, `-ound` and `-atch`, which a hard-mode solver can only work through one guess
+/// at a time. The rest have rare letters or repeat one, like `jazzy` and `fuzzy`.
+///
+/// Every word is in the bundled dictionary and among the real game's answers.
+pub const HARD_ANSWERS: &[&str] = &[
+    "jazzy", "fuzzy", "jolly", "folly", "holly", "dolly", "silly", "pound", "mound", "hound",
+    "wound", "sound", "round", "bound", "found", "watch", "match", "catch", "batch", "hatch",
+    "latch", "patch", "fight", "night", "might", "sight", "light", "tight", "right", "eight",
+    "shave", "share", "shape", "shade", "stare", "snare", "spare", "waste", "paste", "taste",
+    "haste", "vaunt", "daunt", "taunt", "gaunt", "jaunt", "joker", "poker",
+];
+
+pub struct Wordle {
+    /// Every word that may be guessed, and how often it occurs.
+    dictionary: HashMap<Cow<'static, str>, usize>,
+    /// The words that may be the answer, if not all of `dictionary` can be.
+    answers: Option<HashSet<Cow<'static, str>>>,
+}
+
+impl Default for Wordle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Wordle {
+    pub fn new() -> Self {
+        Self::try_new().expect("the bundled dictionary is valid")
+    }
+
+    /// Load the bundled dictionary, with the counts scaled to fit a `usize` as in
+    /// [`DICTIONARY`].
+    ///
+    /// This never returns an error: the bundled dictionary is checked when the crate is built,
+    /// so there is nothing left to go wrong at run time. It returns a `Result` so that it reads
+    /// like [`Wordle::from_dictionary`], and code matching on the error keeps compiling.
+    pub fn try_new() -> Result<Self, DictionaryError> {
+        Ok(Self {
+            dictionary: DICTIONARY
+                .iter()
+                .map(|&(word, count)| (Cow::Borrowed(word), count))
+                .collect(),
+            answers: None,
+        })
+    }
+
+    /// Use the bundled dictionary for `lang`, see [`Lang`]. [`Wordle::new`] is English.
+    pub fn new_lang(lang: Lang) -> Self {
+        if lang == Lang::En {
+            // Already ascii, so it can be borrowed rather than copied.
+            return Self::new();
+        }
+        Self::from_dictionary(&lang.dictionary()).expect("the bundled dictionary is valid")
+    }
+
+    /// Load a custom dictionary made of `word frequency` lines, like the bundled one.
+    /// Blank lines are skipped.
+    pub fn from_dictionary(text: &str) -> Result<Self, DictionaryError> {
+        Ok(Self {
+            dictionary: dictionary::parse(text, 5)?
+                .into_iter()
+                .map(|(word, count)| (Cow::Owned(word.to_string()), count))
+                .collect(),
+            answers: None,
+        })
+    }
+
+    /// Load separate lists of the words that may be guessed and the words that may be the
+    /// answer, as in the real game, both made of `word frequency` lines. Words in `answers` may
+    /// be guessed too, even if they aren't in `guessable`.
+    pub fn with_lists(guessable: &str, answers: &str) -> Result<Self, DictionaryError> {
+        let mut wordle = Self::from_dictionary(guessable)?;
+        let mut words = HashSet::new();
+        for (word, count) in dictionary::parse(answers, 5)? {
+            let word: Cow<'static, str> = Cow::Owned(word.to_string());
+            wordle.dictionary.entry(word.clone()).or_insert(count);
+            words.insert(word);
+        }
+        wordle.answers = Some(words);
+        Ok(wordle)
+    }
+
+    /// The words that may be the answer.
+    fn answers(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        match &self.answers {
+            Some(answers) => Box::new(answers.iter().map(|w| &**w)),
+            None => Box::new(self.dictionary.keys().map(|w| &**w)),
+        }
+    }
+
+    /// How often `word` occurs, according to the dictionary, or `None` if it isn't in it.
+    pub fn frequency(&self, word: &str) -> Option<usize> {
+        self.dictionary.get(word).copied()
+    }
+
+    /// Check a guess typed by a player, ignoring case and surrounding whitespace, and return it
+    /// as it is spelled in the dictionary.
+    pub fn check_guess(&self, input: &str) -> Result<&str, GuessError> {
+        let input = input.trim();
+        if let Some((index, found)) = input.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(GuessError::NonAscii { index, found });
+        }
+        if input.len() != 5 {
+            return Err(GuessError::WrongLength { got: input.len() });
+        }
+        match self.dictionary.get_key_value(&*input.to_ascii_lowercase()) {
+            Some((word, _)) => Ok(word),
+            None => Err(GuessError::NotInDictionary),
+        }
+    }
+
+    /// Whether `word` may be guessed.
+    pub fn contains(&self, word: &str) -> bool {
+        self.dictionary.contains_key(word)
+    }
+
+    /// The number of words that may be guessed.
+    pub fn len(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dictionary.is_empty()
+    }
+
+    /// Every word in the dictionary with how often it occurs, most common first. Words that
+    /// occur equally often are in alphabetical order.
+    pub fn words_by_frequency(&self) -> impl Iterator<Item = (&str, usize)> {
+        let mut words: Vec<(&str, usize)> = self
+            .dictionary
+            .iter()
+            .map(|(word, &count)| (&**word, count))
+            .collect();
+        words.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        words.into_iter()
+    }
+
+    /// Like [`Wordle::from_dictionary`], reading the dictionary from `reader`.
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, DictionaryError> {
+        Self::from_dictionary(&dictionary::read(reader)?)
+    }
+
+    /// Like [`Wordle::from_dictionary`], reading the dictionary from the file at `path`. A
+    /// malformed dictionary is reported as an [`io::ErrorKind::InvalidData`] error.
+    pub fn from_path(path: &Path) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        Self::from_reader(file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// The lowest mean number of guesses any guesser could achieve over this dictionary, with
+    /// every possible answer equally likely and any word allowed as a guess.
+    ///
+    /// This solves the game exactly, which is exponential in the size of the dictionary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_EXACT`](crate::MAX_EXACT) possible answers, which there
+    /// are with the bundled dictionary.
+    pub fn optimal_mean_guesses(&self) -> f64 {
+        let answers: Vec<&str> = self.answers().collect();
+        let words: Vec<&str> = self.dictionary.keys().map(|w| &**w).collect();
+        expected_optimal_guesses(&answers, &words)
+    }
+
+    /// Play a game of up to 32 guesses, and return how many it took, if the answer was found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any guess is not in the dictionary. An `answer` that isn't in it can't be
+    /// found by a guesser that only plays dictionary words, so the game runs out of guesses;
+    /// see [`Wordle::try_play`] for a version that reports it instead.
+    pub fn play<G: Guesser>(&self, answer: &'static str, guesser: G) -> Option<usize> {
+        // Popoki allows at least 32 guesses.
+        self.play_with_limit(answer, guesser, 32)
+    }
+
+    /// Like [`Wordle::play`], but says why a game that wasn't won ended, and ends it rather than
+    /// panicking when the guesser plays a word that isn't in the dictionary.
+    pub fn play_result<G: Guesser>(&self, answer: &str, guesser: G) -> PlayResult {
+        let rules = Rules {
+            max_guesses: 32,
+            hint_every: None,
+            hard_mode: false,
+            unknown_ends_game: true,
+        };
+        match self.play_inner(answer, guesser, rules, &mut |_, _| {}) {
+            Ok((Some(score), _)) => PlayResult::Solved(score),
+            Ok((None, _)) => PlayResult::Exhausted,
+            Err(Stopped::Unknown(guess)) => PlayResult::InvalidGuess(guess),
+            Err(Stopped::HardMode(_)) => unreachable!("only hard mode rejects guesses"),
+        }
+    }
+
+    /// Like [`Wordle::play`], but checks that `answer` is in the dictionary first, since a
+    /// guesser that only plays dictionary words could otherwise never find it.
+    pub fn try_play<G: Guesser>(
+        &self,
+        answer: &'static str,
+        guesser: G,
+    ) -> Result<Option<usize>, PlayError> {
+        if !self.dictionary.contains_key(answer) {
+            return Err(PlayError::AnswerNotInDictionary { answer });
+        }
+        Ok(self.play(answer, guesser))
+    }
+
+    /// Like [`Wordle::play`], but gives up after `max_guesses` guesses rather than 32.
+    ///
+    /// A limit of `0` gives up without asking the guesser for anything.
+    pub fn play_with_limit<G: Guesser>(
+        &self,
+        answer: &str,
+        guesser: G,
+        max_guesses: usize,
+    ) -> Option<usize> {
+        self.play_easy(answer, guesser, max_guesses, None).0
+    }
+
+    /// Like [`Wordle::play`], but calls `observer` as soon as each guess has been made, with the
+    /// turn (counting from 1), the guess, its mask, and how many of the possible answers are
+    /// still consistent with every guess so far.
+    pub fn play_with_observer<G, O>(
+        &self,
+        answer: &str,
+        guesser: G,
+        mut observer: O,
+    ) -> Option<usize>
+    where
+        G: Guesser,
+        O: FnMut(usize, &str, &[Correctness; 5], usize),
+    {
+        let rules = Rules {
+            max_guesses: 32,
+            hint_every: None,
+            hard_mode: false,
+            unknown_ends_game: false,
+        };
+        let mut candidates: Vec<&str> = self.answers().collect();
+        let mut on_turn = |turn: usize, guess: &Guess| {
+            let prepared = guess.prepare();
+            candidates.retain(|word| prepared.allows(word));
+            observer(turn, &guess.word, &guess.mask, candidates.len());
+        };
+        self.play_inner(answer, guesser, rules, &mut on_turn)
+            .expect("only hard mode rejects guesses")
+            .0
+    }
+
+    /// Like [`Wordle::play`], but also records every guess that was made.
+    pub fn play_recording<G: Guesser>(&self, answer: &'static str, guesser: G) -> GuessOutcome {
+        match self.play_easy(answer, guesser, 32, None) {
+            (Some(_), history) => GuessOutcome::Solved(history),
+            (None, history) => GuessOutcome::OutOfGuesses(history),
+        }
+    }
+
+    /// Like [`Wordle::play`], but also returns every guess that was made, including the final
+    /// correct one.
+    #[deprecated(note = "use `Wordle::play_recording`, which says whether the game was solved")]
+    pub fn play_recorded<G: Guesser>(
+        &self,
+        answer: &'static str,
+        guesser: G,
+    ) -> (Option<usize>, Vec<Guess<'static>>) {
+        let outcome = self.play_recording(answer, guesser);
+        (outcome.score(), outcome.into_history())
+    }
+
+    /// Like [`Wordle::play_recording`], but also notes how many candidates the guesser had left
+    /// after each turn, for guessers that say (see [`Guesser::remaining`]).
+    pub fn play_verbose<G: Guesser>(&self, answer: &'static str, guesser: G) -> PlayTrace {
+        let mut tracking = Tracking {
+            inner: guesser,
+            counts: Vec::new(),
+        };
+        let outcome = self.play_recording(answer, &mut tracking);
+        let score = outcome.score();
+        let reports = tracking.counts.first().is_some_and(Option::is_some);
+
+        // The guesser only narrows its candidates down when asked for the next guess, so the
+        // count after each turn is the one it had when making the following guess.
+        let mut after = tracking.counts.into_iter().skip(1);
+        let turns = outcome
+            .into_history()
+            .into_iter()
+            .map(|guess| TurnInfo {
+                guess: guess.word.into_owned(),
+                mask: guess.mask,
+                remaining_after: after.next().flatten(),
+            })
+            .collect::<Vec<_>>();
+        let mut trace = PlayTrace { turns, score };
+        if score.is_some() && reports {
+            // Only the answer is left once it has been guessed.
+            trace.turns.last_mut().unwrap().remaining_after = Some(1);
+        }
+        trace
+    }
+
+    /// Play an easier game where, after every `every` failed guesses, one letter of the answer
+    /// is revealed to the guesser through [`Guesser::reveal`].
+    ///
+    /// Letters are revealed left to right, skipping positions the guesser already has green.
+    /// Hints are free: the score still only counts guesses, so it is directly comparable to
+    /// [`Wordle::play`] but is usually lower.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `every` is zero.
+    pub fn play_with_hints<G: Guesser>(
+        &self,
+        answer: &'static str,
+        guesser: G,
+        every: usize,
+    ) -> Option<usize> {
+        assert_ne!(every, 0, "hints must be spaced by at least one guess");
+        self.play_easy(answer, guesser, 32, Some(every)).0
+    }
+
+    /// Play under real Wordle hard mode, where every guess must be consistent with all the clues
+    /// revealed so far.
+    ///
+    /// The game stops at the first guess that breaks the rule, which is reported as an error.
+    pub fn play_hard_mode<G: Guesser>(
+        &self,
+        answer: &'static str,
+        guesser: G,
+    ) -> Result<Option<usize>, HardModeViolation> {
+        let rules = Rules {
+            max_guesses: 32,
+            hint_every: None,
+            hard_mode: true,
+            unknown_ends_game: false,
+        };
+        match self.play_inner(answer, guesser, rules, &mut |_, _| {}) {
+            Ok((score, _)) => Ok(score),
+            Err(Stopped::HardMode(violation)) => Err(violation),
+            Err(Stopped::Unknown(_)) => unreachable!("unknown guesses panic"),
+        }
+    }
+
+    /// Play against an adversarial host, like Absurdle, and return how many guesses it took.
+    ///
+    /// Rather than picking an answer up front, the host answers each guess with whichever mask
+    /// keeps the most possible answers in play, so it only commits to a word once it has to.
+    /// Between equally large sets it avoids saying the guess is right, and otherwise goes with
+    /// the first mask in [`Correctness::patterns`] order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any guess is not in the dictionary, or if the guesser hasn't won after 32
+    /// guesses.
+    pub fn play_adversarial<G: Guesser>(&self, mut guesser: G) -> usize {
+        let mut candidates: Vec<&str> = self.answers().collect();
+        let mut history: Vec<Guess<'static>> = Vec::new();
+
+        for i in 1..=32 {
+            let guess = guesser.guess(&history);
+            assert!(
+                self.dictionary.contains_key(&*guess),
+                "guess '{}' is not in the dictionary",
+                guess
+            );
+
+            let mut counts = [0usize; Correctness::PATTERN_COUNT];
+            for candidate in &candidates {
+                counts[Correctness::pattern_index(&Correctness::compute(candidate, &guess))] += 1;
+            }
+            let solved = Correctness::pattern_index(&[Correctness::Correct; 5]);
+            let (index, _) = counts
+                .iter()
+                .enumerate()
+                .max_by_key(|&(index, &count)| (count, index != solved, Reverse(index)))
+                .expect("there is at least one pattern");
+            if index == solved {
+                guesser.finish(i);
+                return i;
+            }
+
+            let mask = Correctness::pattern_from_index(index);
+            candidates.retain(|candidate| Correctness::compute(candidate, &guess) == mask);
+            history.push(Guess {
+                word: Cow::Owned(guess),
+                mask,
+            });
+        }
+        panic!("the guesser did not win within 32 guesses");
+    }
+
+    fn play_easy<G: Guesser>(
+        &self,
+        answer: &str,
+        guesser: G,
+        max_guesses: usize,
+        hint_every: Option<usize>,
+    ) -> (Option<usize>, Vec<Guess<'static>>) {
+        let rules = Rules {
+            max_guesses,
+            hint_every,
+            hard_mode: false,
+            unknown_ends_game: false,
+        };
+        self.play_inner(answer, guesser, rules, &mut |_, _| {})
+            .expect("only hard mode rejects guesses")
+    }
+
+    fn play_inner<G: Guesser>(
+        &self,
+        answer: &str,
+        mut guesser: G,
+        rules: Rules,
+        on_turn: &mut dyn FnMut(usize, &Guess),
+    ) -> Result<(Option<usize>, Vec<Guess<'static>>), Stopped> {
+        let mut history: Vec<Guess<'static>> = Vec::new();
+        let mut known = [false; 5];
+
+        for i in 1..=rules.max_guesses {
+            let guess = guesser.guess(&history);
+            if rules.hard_mode && !history.iter().all(|prev| prev.matches(&guess)) {
+                return Err(Stopped::HardMode(HardModeViolation { turn: i, guess }));
+            }
+            if guess == answer {
+                guesser.finish(i);
+                history.push(Guess {
+                    word: Cow::Owned(guess),
+                    mask: [Correctness::Correct; 5],
+                });
+                on_turn(i, &history[i - 1]);
+                return Ok((Some(i), history));
+            }
+            if !self.dictionary.contains_key(&*guess) {
+                // Checked before computing the mask, which a word of the wrong length can't get.
+                assert!(
+                    rules.unknown_ends_game,
+                    "guess '{}' is not in the dictionary",
+                    guess
+                );
+                return Err(Stopped::Unknown(guess));
+            }
+
+            let correctness = Correctness::compute(answer, &guess);
+            for (k, c) in known.iter_mut().zip(correctness) {
+                *k |= c == Correctness::Correct;
+            }
+            history.push(Guess {
+                word: Cow::Owned(guess),
+                mask: correctness,
+            });
+            on_turn(i, &history[i - 1]);
+
+            if rules.hint_every.is_some_and(|every| i % every == 0) {
+                if let Some(position) = known.iter().position(|&k| !k) {
+                    known[position] = true;
+                    guesser.reveal(position, answer.as_bytes()[position]);
+                }
+            }
+        }
+        Ok((None, history))
+    }
+
+    /// The possible answers that are consistent with every guess in `history`, in alphabetical
+    /// order.
+    pub fn candidates(&self, history: &[Guess]) -> Vec<&str> {
+        let mut words = remaining_candidates(self.answers(), history);
+        words.sort_unstable();
+        words
+    }
+
+    /// The answer for puzzle number `day`, the same on every run and every machine for the same
+    /// dictionary (and answer list).
+    ///
+    /// The possible answers are sorted alphabetically, and `day` picks one through the SplitMix64
+    /// mixing function, so consecutive days get unrelated words. Answers may come up again on
+    /// later days. Adding or removing answers changes the mapping for every day.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dictionary is empty.
+    pub fn daily(&self, day: u32) -> &str {
+        let mut answers: Vec<&str> = self.answers().collect();
+        assert!(!answers.is_empty(), "the dictionary is empty");
+        answers.sort_unstable();
+        let z = splitmix64(&mut u64::from(day));
+        answers[(z % answers.len() as u64) as usize]
+    }
+
+    /// How many possible answers are consistent with every guess in `history`, without
+    /// collecting them like [`Wordle::candidates`] does. The history is folded into
+    /// [`Constraints`] first (greens, letters ruled out of each position, and how many of each
+    /// letter there can be), which makes checking each word cheap.
+    ///
+    /// Despite the name, this is exact: the constraints allow precisely the words that every
+    /// guess in the history [matches](Guess::matches).
+    pub fn estimate_remaining(&self, history: &[Guess]) -> usize {
+        let constraints = Constraints::from_history(history);
+        self.answers()
+            .filter(|word| constraints.permits(word))
+            .count()
+    }
+
+    /// Grade a game that was already played, such as by a person, turn by turn: how many
+    /// candidates were left before each guess, and how the guess ranked among every word in the
+    /// dictionary by expected information.
+    ///
+    /// Every remaining candidate counts as equally likely. Between words that are expected to
+    /// give the same information, one that could be the answer ranks higher. Words that tie
+    /// share a rank, so several can be "the best".
+    ///
+    /// This doesn't rank by [`Solver::rank`](crate::Solver::rank) on purpose. The solver ranks
+    /// by its own goodness, which depends on its [`Options`](crate::Options) and word weights,
+    /// and its cutoff and anagram deduplication leave words out, so a person's guess may not be
+    /// ranked at all. Expected information needs nothing but this dictionary, ranks every word,
+    /// and can be checked by hand. To see where a guess stands with a particular solver, look
+    /// it up in [`Solver::rank`](crate::Solver::rank) instead.
+    ///
+    /// Every word is scored against every candidate on each turn, so this takes a while on the
+    /// bundled dictionary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any guess is not in the dictionary.
+    pub fn grade(&self, answer: &str, guesses: &[&str]) -> GameGrade {
+        let mut history: Vec<Guess> = Vec::new();
+        let mut turns = Vec::with_capacity(guesses.len());
+        for &guess in guesses {
+            assert!(
+                self.dictionary.contains_key(guess),
+                "guess '{}' is not in the dictionary",
+                guess
+            );
+
+            let candidates = self.weighed_candidates(&history);
+            let (bits, possible) = information(guess, &candidates);
+            let mut rank = 1;
+            let mut best = (guess, bits, possible);
+            for word in self.dictionary.keys() {
+                let (b, p) = information(word, &candidates);
+                if (b, p) > (bits, possible) {
+                    rank += 1;
+                }
+                if (b, p) > (best.1, best.2) || ((b, p) == (best.1, best.2) && **word < *best.0) {
+                    best = (word, b, p);
+                }
+            }
+
+            turns.push(TurnGrade {
+                guess: guess.to_string(),
+                candidates: candidates.len(),
+                bits,
+                rank,
+                best: best.0.to_string(),
+            });
+            history.push(Guess {
+                word: Cow::Borrowed(guess),
+                mask: Correctness::compute(answer, guess),
+            });
+        }
+        GameGrade { turns }
+    }
+
+    /// Suggest the next guess for someone playing in hard mode: of the words that agree with
+    /// every clue in `history` and haven't been guessed yet, the one expected to tell the
+    /// remaining candidates apart best, ranked as in [`Wordle::grade`].
+    ///
+    /// This is `None` only if no word in the dictionary fits, which can't happen when `answer`
+    /// is in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `history` doesn't match `answer`, or if the game is already won.
+    pub fn hint(&self, answer: &str, history: &[Guess]) -> Option<String> {
+        assert!(
+            history.iter().all(|guess| guess.matches(answer)),
+            "history doesn't match answer '{}'",
+            answer
+        );
+        assert!(
+            history.iter().all(|guess| guess.word != answer),
+            "the game is already won"
+        );
+
+        let candidates = self.weighed_candidates(history);
+        let mut best: Option<(&str, (f64, bool))> = None;
+        for word in self.dictionary.keys() {
+            let word = &**word;
+            if history
+                .iter()
+                .any(|guess| guess.word == word || !guess.matches(word))
+            {
+                continue;
+            }
+            let score = information(word, &candidates);
+            if best.is_none_or(|(w, s)| score > s || (score == s && word < w)) {
+                best = Some((word, score));
+            }
+        }
+        best.map(|(word, _)| word.to_string())
+    }
+
+    /// How many bits of uncertainty each guess in `history` actually removed:
+    /// `log2(before) - log2(after)`, where `before` and `after` are how many possible answers
+    /// agreed with the history before and after that guess. Unlike [`Wordle::grade`] this
+    /// looks at what the mask turned out to be, not what was expected.
+    ///
+    /// A guess that leaves no candidate at all, because the history contradicts itself or the
+    /// answer isn't in the dictionary, gains infinitely many bits.
+    pub fn information_gained(&self, history: &[Guess]) -> Vec<f64> {
+        let mut candidates: Vec<&str> = self.answers().collect();
+        history
+            .iter()
+            .map(|guess| {
+                let before = candidates.len() as f64;
+                candidates.retain(|word| guess.matches(word));
+                before.log2() - (candidates.len() as f64).log2()
+            })
+            .collect()
+    }
+
+    /// [`Wordle::candidates`], each counting as equally likely.
+    fn weighed_candidates(&self, history: &[Guess]) -> Vec<(&str, usize)> {
+        self.candidates(history)
+            .into_iter()
+            .map(|word| (word, 1))
+            .collect()
+    }
+
+    /// Play every one of `answers` with a fresh guesser from `make_guesser`, and summarize the
+    /// scores, along with how long it took.
+    pub fn evaluate<G: Guesser>(
+        &self,
+        make_guesser: impl Fn() -> G,
+        answers: &[&'static str],
+    ) -> Summary {
+        let start = Instant::now();
+        let scores: Vec<_> = self
+            .games(answers, make_guesser)
+            .map(|(_, score)| score)
+            .collect();
+        Summary::new(&scores, start.elapsed())
+    }
+
+    /// Like [`Wordle::evaluate`], but plays the games on all cores, with a fresh guesser from
+    /// `make_guesser` for each, made on the thread that plays it.
+    ///
+    /// `on_progress(done, total)` is called as each game finishes, from whichever thread played
+    /// it, so calls can arrive slightly out of order. The summary is the same as
+    /// [`Wordle::evaluate`] gives (see [`Summary::merge`]), apart from the time taken.
+    #[cfg(feature = "parallel")]
+    pub fn evaluate_parallel<G: Guesser>(
+        &self,
+        make_guesser: impl Fn() -> G + Sync,
+        answers: &[&'static str],
+        on_progress: impl Fn(usize, usize) + Sync,
+    ) -> Summary {
+        use rayon::prelude::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let start = Instant::now();
+        let done = AtomicUsize::new(0);
+        let summary = answers
+            .par_iter()
+            .map(|&answer| {
+                let summary = Summary::new(&[self.play(answer, make_guesser())], Duration::ZERO);
+                on_progress(done.fetch_add(1, Ordering::Relaxed) + 1, answers.len());
+                summary
+            })
+            .reduce(Summary::default, |a, b| a.merge(&b));
+        Summary {
+            elapsed: start.elapsed(),
+            ..summary
+        }
+    }
+
+    /// [`Wordle::evaluate`] over [`HARD_ANSWERS`] only, which is quick enough to run after
+    /// every change to a solver and concentrates on the games it is most likely to lose.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`Wordle::play`] if any of them, or any guess, is not in the dictionary.
+    pub fn evaluate_hard<G: Guesser, F: Fn() -> G>(&self, make_guesser: F) -> Summary {
+        self.evaluate(make_guesser, HARD_ANSWERS)
+    }
+
+    /// Lazily play every one of `answers` with a fresh guesser from `make_guesser`, yielding
+    /// each answer along with its score as [`Wordle::play`] returns it.
+    pub fn games<'a, G: Guesser>(
+        &'a self,
+        answers: &'a [&'static str],
+        mut make_guesser: impl FnMut() -> G + 'a,
+    ) -> impl Iterator<Item = (&'static str, Option<usize>)> + 'a {
+        answers
+            .iter()
+            .map(move |&answer| (answer, self.play(answer, make_guesser())))
+    }
+
+    /// Like [`Wordle::evaluate`], but plays every game with the same `guesser`, calling
+    /// [`Guesser::reset`] before each one, so it only has to be set up once.
+    pub fn evaluate_reusing<G: Guesser>(
+        &self,
+        guesser: &mut G,
+        answers: &[&'static str],
+    ) -> Summary {
+        let start = Instant::now();
+        let scores: Vec<_> = answers
+            .iter()
+            .map(|&answer| {
+                guesser.reset();
+                self.play(answer, Reusing(&mut *guesser))
+            })
+            .collect();
+        Summary::new(&scores, start.elapsed())
+    }
+
+    /// Play every possible answer with a fresh guesser from `make_guesser`, allowing at most
+    /// `max` guesses each (see [`Wordle::play_with_limit`]), and sort them by whether they were
+    /// found.
+    pub fn coverage<G: Guesser, F: FnMut() -> G>(
+        &self,
+        mut make_guesser: F,
+        max: usize,
+    ) -> CoverageReport<'_> {
+        let mut coverage = CoverageReport {
+            max,
+            solved: Vec::new(),
+            failed: Vec::new(),
+        };
+        for answer in self.answers() {
+            match self.play_with_limit(answer, make_guesser(), max) {
+                Some(_) => coverage.solved.push(answer),
+                None => coverage.failed.push(answer),
+            }
+        }
+        coverage.solved.sort_unstable();
+        coverage.failed.sort_unstable();
+        coverage
+    }
+
+    /// Play every one of `answers` with a fresh guesser from `make_guesser`, and check that each
+    /// is solved in at most `max` guesses.
+    ///
+    /// Meant for catching strategy regressions in CI. On failure, every answer that took too
+    /// long (or was never found) is returned along with all the guesses made for it.
+    pub fn assert_solves_within<G: Guesser>(
+        &self,
+        answers: &[&'static str],
+        mut make_guesser: impl FnMut() -> G,
+        max: usize,
+    ) -> Result<(), Vec<(&'static str, Vec<Guess<'static>>)>> {
+        let failures: Vec<_> = answers
+            .iter()
+            .filter_map(|&answer| {
+                let outcome = self.play_recording(answer, make_guesser());
+                match outcome.score() {
+                    Some(score) if score <= max => None,
+                    _ => Some((answer, outcome.into_history())),
+                }
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+/// Which answers a guesser finds within a number of guesses, see [`Wordle::coverage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport<'a> {
+    /// The most guesses each game was allowed.
+    pub max: usize,
+    /// The answers found within `max` guesses, in alphabetical order.
+    pub solved: Vec<&'a str>,
+    /// The answers that weren't, in alphabetical order.
+    pub failed: Vec<&'a str>,
+}
+
+impl CoverageReport<'_> {
+    /// Whether every answer was found.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// How many bits guessing `word` is expected to give about `candidates`, and whether it is one
+/// of them, which breaks ties in its favor.
+fn information(word: &str, candidates: &[(&str, usize)]) -> (f64, bool) {
+    let bits = Entropy.score(&partition(word, candidates));
+    (bits, candidates.iter().any(|&(c, _)| c == word))
+}
+
+struct Rules {
+    max_guesses: usize,
+    hint_every: Option<usize>,
+    hard_mode: bool,
+    /// Whether a guess outside the dictionary ends the game with [`Stopped::Unknown`], rather
+    /// than panicking.
+    unknown_ends_game: bool,
+}
+
+/// Why [`Wordle::play_inner`] ended a game before it was won or out of guesses.
+#[derive(Debug)]
+enum Stopped {
+    HardMode(HardModeViolation),
+    /// A guess that isn't in the dictionary, see [`Rules::unknown_ends_game`].
+    Unknown(String),
+}
+
+/// How a game played with [`Wordle::play_result`] ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub enum PlayResult {
+    /// The answer was found in this many guesses.
+    Solved(usize),
+    /// The guesser ran out of guesses.
+    Exhausted,
+    /// The guesser played this word, which isn't in the dictionary.
+    InvalidGuess(String),
+}
+
+/// Why a game could not be played with [`Wordle::try_play`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlayError {
+    /// The answer is not in the dictionary, so it could never be guessed.
+    AnswerNotInDictionary { answer: &'static str },
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AnswerNotInDictionary { answer } => {
+                write!(f, "answer '{}' is not in the dictionary", answer)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+/// Why [`Wordle::check_guess`] turned a guess down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    /// The guess isn't five letters long.
+    WrongLength { got: usize },
+    /// The character at `index` (counting characters from 0) is not ascii, like an accented
+    /// letter.
+    NonAscii { index: usize, found: char },
+    /// The guess is five ascii characters, but not a word in the dictionary.
+    NotInDictionary,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { got } => write!(f, "guess has {} letters, expected 5", got),
+            Self::NonAscii { index, found } => {
+                write!(
+                    f,
+                    "'{}' at position {} is not an ascii letter",
+                    found, index
+                )
+            }
+            Self::NotInDictionary => write!(f, "not in the dictionary"),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// A guess that ignored an earlier clue in [`Wordle::play_hard_mode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardModeViolation {
+    /// The turn the guess was made on, counting from 1.
+    pub turn: usize,
+    pub guess: String,
+}
+
+impl fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "guess '{}' on turn {} ignores earlier clues",
+            self.guess, self.turn
+        )
+    }
+}
+
+impl std::error::Error for HardModeViolation {}
+
+/// How a game played with [`Wordle::play_verbose`] went, turn by turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayTrace {
+    pub turns: Vec<TurnInfo>,
+    /// The number of guesses it took, as returned by [`Wordle::play`].
+    pub score: Option<usize>,
+}
+
+/// One turn of a [`PlayTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnInfo {
+    pub guess: String,
+    pub mask: [Correctness; 5],
+    /// How many candidates the guesser had left once it took this turn's mask into account, if
+    /// it says (see [`Guesser::remaining`]). Always `None` for the last turn of a failed game,
+    /// since the guesser isn't asked to guess again.
+    pub remaining_after: Option<usize>,
+}
+
+/// How good each guess of a game was, as found by [`Wordle::grade`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameGrade {
+    pub turns: Vec<TurnGrade>,
+}
+
+/// One turn of a [`GameGrade`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnGrade {
+    pub guess: String,
+    /// How many words could still have been the answer when the guess was made.
+    pub candidates: usize,
+    /// The information the guess was expected to give, in bits.
+    pub bits: f64,
+    /// Where the guess ranked among all words, from 1 for (one of) the best.
+    pub rank: usize,
+    /// The best word to guess, alphabetically first if several were as good.
+    pub best: String,
+}
+
+/// Records [`Guesser::remaining`] each time the guesser is asked for a guess.
+struct Tracking<G> {
+    inner: G,
+    counts: Vec<Option<usize>>,
+}
+
+impl<G: Guesser> Guesser for &mut Tracking<G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        let guess = self.inner.guess(history);
+        self.counts.push(self.inner.remaining());
+        guess
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.inner.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.inner.reveal(position, letter);
+    }
+}
+
+/// Lends a guesser to a single game without giving it up.
+struct Reusing<'g, G>(&'g mut G);
+
+impl<G: Guesser> Guesser for Reusing<'_, G> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        self.0.guess(history)
+    }
+
+    fn finish(&self, guesses: usize) {
+        self.0.finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        self.0.reveal(position, letter);
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.0.remaining()
+    }
+}
+
+/// How a game played with [`Wordle::play_recording`] went, along with every guess made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessOutcome {
+    /// The answer was found. The last guess is the answer itself, with an all-correct mask.
+    Solved(Vec<Guess<'static>>),
+    /// The guesser ran out of guesses.
+    OutOfGuesses(Vec<Guess<'static>>),
+}
+
+impl GuessOutcome {
+    /// The number of guesses it took, as returned by [`Wordle::play`].
+    pub fn score(&self) -> Option<usize> {
+        match self {
+            Self::Solved(history) => Some(history.len()),
+            Self::OutOfGuesses(_) => None,
+        }
+    }
+
+    pub fn history(&self) -> &[Guess<'static>] {
+        match self {
+            Self::Solved(history) | Self::OutOfGuesses(history) => history,
+        }
+    }
+
+    pub fn into_history(self) -> Vec<Guess<'static>> {
+        match self {
+            Self::Solved(history) | Self::OutOfGuesses(history) => history,
+        }
+    }
+}
+
+/// The words from `dict` that are consistent with every guess in `history`, in the same order.
+pub fn remaining_candidates<'a>(
+    dict: impl IntoIterator<Item = &'a str>,
+    history: &[Guess],
+) -> Vec<&'a str> {
+    dict.into_iter()
+        .filter(|word| history.iter().all(|guess| guess.matches(word)))
+        .collect()
+}
+
+/// Returns the index of the first entry in `history` that rules out `word` as the answer, or
+/// `None` if `word` is consistent with every guess so far.
+pub fn rejected_by(history: &[Guess], word: &str) -> Option<usize> {
+    history.iter().position(|guess| !guess.matches(word))
+}
+
+pub trait Guesser {
+    fn guess(&mut self, history: &[Guess]) -> String;
+
+    /// Like [`Guesser::guess`], but with `budget` to come up with the guess, as used by
+    /// [`TimeoutGuesser`]. Guessers that can't stop early ignore the budget, which is the
+    /// default; [`Solver`](crate::Solver) plays the best guess it found in time.
+    fn guess_within(&mut self, history: &[Guess], _budget: Duration) -> String {
+        self.guess(history)
+    }
+
+    fn finish(&self, _guesses: usize) {}
+
+    /// Told that the answer has `letter` at `position`, in games with hints (see
+    /// [`Wordle::play_with_hints`]). Guessers are free to ignore this.
+    fn reveal(&mut self, _position: usize, _letter: u8) {}
+
+    /// Get ready to play a new game, as used by [`Wordle::evaluate_reusing`].
+    ///
+    /// After a reset, the guesser must play exactly as a freshly constructed one would, but it
+    /// may hold on to anything that doesn't depend on the game, like allocations and caches.
+    /// Guessers that keep no state between turns don't need to do anything, which is the
+    /// default.
+    fn reset(&mut self) {}
+
+    /// How many candidates the guesser had left when making its latest guess, if it keeps
+    /// track. Used by [`Wordle::play_verbose`].
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Guess for several boards at once (see [`MultiWordle`](crate::MultiWordle)), given the
+    /// history of each board that is still unsolved.
+    ///
+    /// By default this plays for the first unsolved board alone. Guessers that keep their own
+    /// state between turns, like [`Solver`](crate::Solver), will lose track when that board is
+    /// solved and attention moves to the next one, so they should override this.
+    fn guess_multi(&mut self, boards: &[Vec<Guess>]) -> String {
+        self.guess(&boards[0])
+    }
+}
+
+/// Adaptors for any [`Guesser`].
+pub trait GuesserExt: Guesser + Sized {
+    /// Box the guesser, so guessers of different types can be stored and played alike.
+    ///
+    /// ```
+    /// # use popoki::{Guesser, GuesserExt, Solver, Wordle};
+    /// let guessers = [Solver::default().boxed(), popoki::algorithms::Random::new(7).boxed()];
+    /// for guesser in guessers {
+    ///     assert!(Wordle::new().play("cigar", guesser).is_some());
+    /// }
+    /// ```
+    fn boxed<'a>(self) -> Box<dyn Guesser + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(self)
+    }
+
+    /// Give the guesser `budget` for each guess, see [`Guesser::guess_within`].
+    fn with_timeout(self, budget: Duration) -> TimeoutGuesser<Self> {
+        TimeoutGuesser::new(self, budget)
+    }
+}
+
+impl<G: Guesser> GuesserExt for G {}
+
+impl Guesser for Box<dyn Guesser + '_> {
+    fn guess(&mut self, history: &[Guess]) -> String {
+        (**self).guess(history)
+    }
+
+    fn guess_within(&mut self, history: &[Guess], budget: Duration) -> String {
+        (**self).guess_within(history, budget)
+    }
+
+    fn finish(&self, guesses: usize) {
+        (**self).finish(guesses);
+    }
+
+    fn reveal(&mut self, position: usize, letter: u8) {
+        (**self).reveal(position, letter);
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        (**self).remaining()
+    }
+
+    fn guess_multi(&mut self, boards: &[Vec<Guess>]) -> String {
+        (**self).guess_multi(boards)
+    }
+}
+
+/// Any closure (or `fn`) from the history to a guess is a guesser, so state can be captured:
+///
+/// ```
+/// # use popoki::{Guess, Wordle};
+/// let mut words = vec!["right", "wrong"];
+/// let score = Wordle::new().play("right", |_history: &[Guess]| words.pop().unwrap().to_string());
+/// assert_eq!(score, Some(2));
+/// ```
+///
+/// This replaces the impl that used to exist for `fn` pointers alone, which the blanket impl
+/// would otherwise overlap with.
+impl<F> Guesser for F
+where
+    F: FnMut(&[Guess]) -> String,
+{
+    fn guess(&mut self, history: &[Guess]) -> String {
+        self(history)
+    }
+}